@@ -0,0 +1,859 @@
+//! # RoleToken Example Contract
+//!
+//! A SEP-41 token whose privileged functions are gated by roles in an `RbacContract`:
+//! - `mint` requires `MINTER`
+//! - `burn_from` requires `BURNER` (and the holder's allowance, as in SEP-41)
+//! - `pause` / `unpause` require `PAUSER`
+//!
+//! ## Role Hierarchy
+//! The constructor takes a freshly deployed, uninitialized RBAC contract and sets it up:
+//!
+//! ```text
+//! DEF_ADMIN ── TKN_ADMIN ─┬─ MINTER
+//!                         ├─ BURNER
+//!                         └─ PAUSER
+//! ```
+//!
+//! `admin` receives `DEF_ADMIN` and `TKN_ADMIN`. The token initializes RBAC as itself
+//! only to create the roles, then renounces `DEF_ADMIN`, so no contract keeps admin
+//! rights. If the RBAC contract was already initialized, deployment fails.
+//!
+//! ## Usage Flow
+//! 1. Deploy an RBAC contract (do not initialize it)
+//! 2. Deploy RoleToken with the admin, the RBAC address and the token metadata
+//! 3. `TKN_ADMIN` grants MINTER, BURNER and PAUSER in RBAC, optionally with an expiry
+//!
+//! ## Pausing
+//! While paused, `transfer`, `transfer_from`, `mint`, `burn` and `burn_from` fail with
+//! `Paused`. `approve` still works so allowances can be prepared.
+
+#![no_std]
+
+use soroban_sdk::token::TokenInterface;
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contracttype, panic_with_error, symbol_short, Address,
+    Env, MuxedAddress, String, Symbol,
+};
+use stellar_keystone_rbac_client::{RbacClient, DEFAULT_ADMIN_ROLE};
+
+// =============================================================================
+// Constants
+// =============================================================================
+
+/// Administers MINTER, BURNER and PAUSER
+pub const TOKEN_ADMIN_ROLE: Symbol = symbol_short!("TKN_ADMIN");
+
+/// The role required to mint
+pub const MINTER_ROLE: Symbol = symbol_short!("MINTER");
+
+/// The role required to burn from other holders
+pub const BURNER_ROLE: Symbol = symbol_short!("BURNER");
+
+/// The role required to pause and unpause the token
+pub const PAUSER_ROLE: Symbol = symbol_short!("PAUSER");
+
+/// Balances are kept alive for about 30 days at 5s per ledger
+const BALANCE_BUMP_LEDGERS: u32 = 518_400;
+
+/// The instance (RBAC address, metadata, pause flag) is kept alive as long as balances
+const INSTANCE_BUMP_LEDGERS: u32 = BALANCE_BUMP_LEDGERS;
+
+// =============================================================================
+// Storage Keys
+// =============================================================================
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    /// The RBAC contract address (instance)
+    RbacAddress,
+    /// Token name, symbol and decimals (instance)
+    Metadata,
+    /// Whether the token is paused (instance)
+    Paused,
+    /// Maps holder to its balance (persistent)
+    Balance(Address),
+    /// Maps (from, spender) to its `Allowance` (temporary)
+    Allowance(Address, Address),
+}
+
+/// Token metadata.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Metadata {
+    pub name: String,
+    pub symbol: String,
+    pub decimals: u32,
+}
+
+/// An allowance and the last ledger it can be used in.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Allowance {
+    pub amount: i128,
+    pub expiration_ledger: u32,
+}
+
+// =============================================================================
+// Errors
+// =============================================================================
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum TokenError {
+    /// Caller lacks the role required for this function
+    NotAuthorized = 1,
+    /// Token is paused
+    Paused = 2,
+    /// Holder balance is too low
+    InsufficientBalance = 3,
+    /// Spender allowance is too low
+    InsufficientAllowance = 4,
+    /// Amount is negative
+    InvalidAmount = 5,
+    /// Allowance expiration is in the past
+    InvalidExpiration = 6,
+}
+
+// =============================================================================
+// Contract
+// =============================================================================
+
+#[contract]
+pub struct RoleToken;
+
+#[contractimpl]
+impl RoleToken {
+    /// Create the token and its role hierarchy.
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    /// * `admin` - Receives DEF_ADMIN and TKN_ADMIN in RBAC
+    /// * `rbac_address` - A deployed, uninitialized RBAC contract
+    /// * `name` - Token name
+    /// * `symbol` - Token symbol
+    /// * `decimals` - Number of decimals
+    ///
+    /// # Panics
+    /// If the RBAC contract is already initialized.
+    pub fn __constructor(
+        env: Env,
+        admin: Address,
+        rbac_address: Address,
+        name: String,
+        symbol: String,
+        decimals: u32,
+    ) {
+        let rbac = RbacClient::new(&env, &rbac_address);
+        let this = env.current_contract_address();
+
+        // The token is the direct invoker, so its own auth is implicit
+        rbac.initialize(&this);
+        rbac.create_role(&this, &TOKEN_ADMIN_ROLE, &DEFAULT_ADMIN_ROLE);
+        for role in [MINTER_ROLE, BURNER_ROLE, PAUSER_ROLE] {
+            rbac.create_role(&this, &role, &TOKEN_ADMIN_ROLE);
+        }
+        rbac.grant_role(&this, &DEFAULT_ADMIN_ROLE, &admin, &0);
+        rbac.grant_role(&this, &TOKEN_ADMIN_ROLE, &admin, &0);
+        rbac.renounce_role(&this, &DEFAULT_ADMIN_ROLE);
+
+        env.storage()
+            .instance()
+            .set(&DataKey::RbacAddress, &rbac_address);
+        env.storage().instance().set(
+            &DataKey::Metadata,
+            &Metadata {
+                name,
+                symbol,
+                decimals,
+            },
+        );
+        Self::extend_instance_ttl(&env);
+    }
+
+    /// Mint new tokens.
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    /// * `minter` - The address minting (must hold MINTER)
+    /// * `to` - The recipient
+    /// * `amount` - Amount to mint
+    ///
+    /// # Authorization
+    /// Minter must authorize and hold MINTER in RBAC.
+    pub fn mint(env: Env, minter: Address, to: Address, amount: i128) -> Result<(), TokenError> {
+        minter.require_auth();
+        Self::require_role(&env, &MINTER_ROLE, &minter)?;
+        Self::ensure_not_paused(&env)?;
+        Self::check_amount(amount)?;
+
+        Self::extend_instance_ttl(&env);
+        Self::credit(&env, &to, amount);
+        Self::emit(&env, (symbol_short!("mint"), to), amount);
+        Ok(())
+    }
+
+    /// Pause transfers, minting and burning.
+    ///
+    /// # Authorization
+    /// Pauser must authorize and hold PAUSER in RBAC.
+    pub fn pause(env: Env, pauser: Address) -> Result<(), TokenError> {
+        Self::set_paused(&env, &pauser, true)
+    }
+
+    /// Resume transfers, minting and burning.
+    ///
+    /// # Authorization
+    /// Pauser must authorize and hold PAUSER in RBAC.
+    pub fn unpause(env: Env, pauser: Address) -> Result<(), TokenError> {
+        Self::set_paused(&env, &pauser, false)
+    }
+
+    /// `true` if the token is paused.
+    pub fn is_paused(env: Env) -> bool {
+        env.storage()
+            .instance()
+            .get(&DataKey::Paused)
+            .unwrap_or(false)
+    }
+
+    /// Get the RBAC contract address.
+    pub fn get_rbac_address(env: Env) -> Address {
+        env.storage().instance().get(&DataKey::RbacAddress).unwrap()
+    }
+
+    // =========================================================================
+    // Internal Helpers
+    // =========================================================================
+
+    fn set_paused(env: &Env, pauser: &Address, paused: bool) -> Result<(), TokenError> {
+        pauser.require_auth();
+        Self::require_role(env, &PAUSER_ROLE, pauser)?;
+
+        env.storage().instance().set(&DataKey::Paused, &paused);
+        Self::extend_instance_ttl(env);
+        let name = if paused {
+            symbol_short!("pause")
+        } else {
+            symbol_short!("unpause")
+        };
+        Self::emit(env, (name,), pauser.clone());
+        Ok(())
+    }
+
+    /// Require `account`'s signature and `role` through RBAC's `authorize`.
+    fn require_role(env: &Env, role: &Symbol, account: &Address) -> Result<(), TokenError> {
+        let rbac = RbacClient::new(env, &Self::get_rbac_address(env.clone()));
+        if rbac.try_authorize(role, account).is_err() {
+            return Err(TokenError::NotAuthorized);
+        }
+        Ok(())
+    }
+
+    fn extend_instance_ttl(env: &Env) {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_BUMP_LEDGERS, INSTANCE_BUMP_LEDGERS);
+    }
+
+    fn ensure_not_paused(env: &Env) -> Result<(), TokenError> {
+        if Self::is_paused(env.clone()) {
+            return Err(TokenError::Paused);
+        }
+        Ok(())
+    }
+
+    fn check_amount(amount: i128) -> Result<(), TokenError> {
+        if amount < 0 {
+            return Err(TokenError::InvalidAmount);
+        }
+        Ok(())
+    }
+
+    fn read_balance(env: &Env, id: &Address) -> i128 {
+        let key = DataKey::Balance(id.clone());
+        match env.storage().persistent().get::<_, i128>(&key) {
+            Some(balance) => {
+                env.storage().persistent().extend_ttl(
+                    &key,
+                    BALANCE_BUMP_LEDGERS,
+                    BALANCE_BUMP_LEDGERS,
+                );
+                balance
+            }
+            None => 0,
+        }
+    }
+
+    fn write_balance(env: &Env, id: &Address, balance: i128) {
+        let key = DataKey::Balance(id.clone());
+        env.storage().persistent().set(&key, &balance);
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, BALANCE_BUMP_LEDGERS, BALANCE_BUMP_LEDGERS);
+    }
+
+    fn credit(env: &Env, id: &Address, amount: i128) {
+        let balance = Self::read_balance(env, id);
+        Self::write_balance(env, id, balance + amount);
+    }
+
+    fn debit(env: &Env, id: &Address, amount: i128) -> Result<(), TokenError> {
+        let balance = Self::read_balance(env, id);
+        if balance < amount {
+            return Err(TokenError::InsufficientBalance);
+        }
+        Self::write_balance(env, id, balance - amount);
+        Ok(())
+    }
+
+    fn read_allowance(env: &Env, from: &Address, spender: &Address) -> Allowance {
+        let key = DataKey::Allowance(from.clone(), spender.clone());
+        match env.storage().temporary().get::<_, Allowance>(&key) {
+            Some(allowance) if allowance.expiration_ledger >= env.ledger().sequence() => allowance,
+            _ => Allowance {
+                amount: 0,
+                expiration_ledger: 0,
+            },
+        }
+    }
+
+    fn spend_allowance(
+        env: &Env,
+        from: &Address,
+        spender: &Address,
+        amount: i128,
+    ) -> Result<(), TokenError> {
+        let mut allowance = Self::read_allowance(env, from, spender);
+        if allowance.amount < amount {
+            return Err(TokenError::InsufficientAllowance);
+        }
+        if amount > 0 {
+            allowance.amount -= amount;
+            env.storage().temporary().set(
+                &DataKey::Allowance(from.clone(), spender.clone()),
+                &allowance,
+            );
+        }
+        Ok(())
+    }
+
+    fn metadata(env: &Env) -> Metadata {
+        env.storage().instance().get(&DataKey::Metadata).unwrap()
+    }
+
+    #[allow(deprecated)]
+    fn emit<T, D>(env: &Env, topics: T, data: D)
+    where
+        T: soroban_sdk::Topics,
+        D: soroban_sdk::IntoVal<Env, soroban_sdk::Val>,
+    {
+        env.events().publish(topics, data);
+    }
+}
+
+#[contractimpl]
+impl TokenInterface for RoleToken {
+    fn allowance(env: Env, from: Address, spender: Address) -> i128 {
+        Self::read_allowance(&env, &from, &spender).amount
+    }
+
+    fn approve(env: Env, from: Address, spender: Address, amount: i128, expiration_ledger: u32) {
+        from.require_auth();
+        if let Err(err) = Self::check_amount(amount) {
+            panic_with_error!(&env, err);
+        }
+        if amount > 0 && expiration_ledger < env.ledger().sequence() {
+            panic_with_error!(&env, TokenError::InvalidExpiration);
+        }
+
+        Self::extend_instance_ttl(&env);
+        let key = DataKey::Allowance(from.clone(), spender.clone());
+        env.storage().temporary().set(
+            &key,
+            &Allowance {
+                amount,
+                expiration_ledger,
+            },
+        );
+        if amount > 0 {
+            let live_for = expiration_ledger - env.ledger().sequence();
+            env.storage()
+                .temporary()
+                .extend_ttl(&key, live_for, live_for);
+        }
+        Self::emit(
+            &env,
+            (symbol_short!("approve"), from, spender),
+            (amount, expiration_ledger),
+        );
+    }
+
+    fn balance(env: Env, id: Address) -> i128 {
+        Self::read_balance(&env, &id)
+    }
+
+    fn transfer(env: Env, from: Address, to: MuxedAddress, amount: i128) {
+        from.require_auth();
+        let to = to.address();
+        let result = Self::ensure_not_paused(&env)
+            .and_then(|_| Self::check_amount(amount))
+            .and_then(|_| Self::debit(&env, &from, amount));
+        if let Err(err) = result {
+            panic_with_error!(&env, err);
+        }
+        Self::extend_instance_ttl(&env);
+
+        Self::credit(&env, &to, amount);
+        Self::emit(&env, (symbol_short!("transfer"), from, to), amount);
+    }
+
+    fn transfer_from(env: Env, spender: Address, from: Address, to: Address, amount: i128) {
+        spender.require_auth();
+        let result = Self::ensure_not_paused(&env)
+            .and_then(|_| Self::check_amount(amount))
+            .and_then(|_| Self::spend_allowance(&env, &from, &spender, amount))
+            .and_then(|_| Self::debit(&env, &from, amount));
+        if let Err(err) = result {
+            panic_with_error!(&env, err);
+        }
+        Self::extend_instance_ttl(&env);
+
+        Self::credit(&env, &to, amount);
+        Self::emit(&env, (symbol_short!("transfer"), from, to), amount);
+    }
+
+    fn burn(env: Env, from: Address, amount: i128) {
+        from.require_auth();
+        let result = Self::ensure_not_paused(&env)
+            .and_then(|_| Self::check_amount(amount))
+            .and_then(|_| Self::debit(&env, &from, amount));
+        if let Err(err) = result {
+            panic_with_error!(&env, err);
+        }
+        Self::extend_instance_ttl(&env);
+
+        Self::emit(&env, (symbol_short!("burn"), from), amount);
+    }
+
+    /// Burn from `from` using the spender's allowance. The spender must also hold BURNER.
+    fn burn_from(env: Env, spender: Address, from: Address, amount: i128) {
+        spender.require_auth();
+        let result = Self::require_role(&env, &BURNER_ROLE, &spender)
+            .and_then(|_| Self::ensure_not_paused(&env))
+            .and_then(|_| Self::check_amount(amount))
+            .and_then(|_| Self::spend_allowance(&env, &from, &spender, amount))
+            .and_then(|_| Self::debit(&env, &from, amount));
+        if let Err(err) = result {
+            panic_with_error!(&env, err);
+        }
+        Self::extend_instance_ttl(&env);
+
+        Self::emit(&env, (symbol_short!("burn"), from), amount);
+    }
+
+    fn decimals(env: Env) -> u32 {
+        Self::metadata(&env).decimals
+    }
+
+    fn name(env: Env) -> String {
+        Self::metadata(&env).name
+    }
+
+    fn symbol(env: Env) -> String {
+        Self::metadata(&env).symbol
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use soroban_sdk::testutils::storage::Instance as _;
+    use soroban_sdk::testutils::{Address as _, Events, Ledger};
+    use soroban_sdk::token::TokenClient;
+    use soroban_sdk::{vec, IntoVal};
+    use stellar_keystone_rbac::{RbacContract, RbacContractClient};
+
+    struct Setup {
+        env: Env,
+        admin: Address,
+        rbac: RbacContractClient<'static>,
+        token: RoleTokenClient<'static>,
+    }
+
+    /// Deploy an uninitialized RBAC contract and a token bound to it.
+    fn setup() -> Setup {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let admin = Address::generate(&env);
+        let rbac_id = env.register(RbacContract, ());
+        let token_id = env.register(
+            RoleToken,
+            (
+                admin.clone(),
+                rbac_id.clone(),
+                String::from_str(&env, "Role Token"),
+                String::from_str(&env, "ROLE"),
+                7u32,
+            ),
+        );
+        let rbac = RbacContractClient::new(&env, &rbac_id);
+        let token = RoleTokenClient::new(&env, &token_id);
+        Setup {
+            env,
+            admin,
+            rbac,
+            token,
+        }
+    }
+
+    /// Grant `role` to a new account and return it.
+    fn grant(s: &Setup, role: &Symbol, expiry: u64) -> Address {
+        let account = Address::generate(&s.env);
+        s.rbac.grant_role(&s.admin, role, &account, &expiry);
+        account
+    }
+
+    #[test]
+    fn test_constructor_sets_up_hierarchy() {
+        let s = setup();
+        let this = s.token.address.clone();
+
+        assert_eq!(s.rbac.get_role_admin(&TOKEN_ADMIN_ROLE), DEFAULT_ADMIN_ROLE);
+        assert_eq!(s.rbac.get_role_admin(&MINTER_ROLE), TOKEN_ADMIN_ROLE);
+        assert_eq!(s.rbac.get_role_admin(&BURNER_ROLE), TOKEN_ADMIN_ROLE);
+        assert_eq!(s.rbac.get_role_admin(&PAUSER_ROLE), TOKEN_ADMIN_ROLE);
+
+        assert!(s.rbac.has_role(&DEFAULT_ADMIN_ROLE, &s.admin));
+        assert!(s.rbac.has_role(&TOKEN_ADMIN_ROLE, &s.admin));
+        // The token renounced the admin role it initialized RBAC with
+        assert!(!s.rbac.has_role(&DEFAULT_ADMIN_ROLE, &this));
+
+        assert_eq!(s.token.get_rbac_address(), s.rbac.address);
+        assert_eq!(s.token.name(), String::from_str(&s.env, "Role Token"));
+        assert_eq!(s.token.symbol(), String::from_str(&s.env, "ROLE"));
+        assert_eq!(s.token.decimals(), 7);
+        assert!(!s.token.is_paused());
+    }
+
+    #[test]
+    fn test_token_admin_manages_operational_roles() {
+        let s = setup();
+        let ops = grant(&s, &TOKEN_ADMIN_ROLE, 0);
+
+        // A second TKN_ADMIN can grant MINTER without being DEF_ADMIN
+        let minter = Address::generate(&s.env);
+        s.rbac.grant_role(&ops, &MINTER_ROLE, &minter, &0);
+        assert!(s.rbac.has_role(&MINTER_ROLE, &minter));
+
+        // A MINTER cannot grant its own role onward
+        let other = Address::generate(&s.env);
+        assert!(s
+            .rbac
+            .try_grant_role(&minter, &MINTER_ROLE, &other, &0)
+            .is_err());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_constructor_with_initialized_rbac_fails() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let rbac_id = env.register(RbacContract, ());
+        RbacContractClient::new(&env, &rbac_id).initialize(&Address::generate(&env));
+        env.register(
+            RoleToken,
+            (
+                Address::generate(&env),
+                rbac_id,
+                String::from_str(&env, "Role Token"),
+                String::from_str(&env, "ROLE"),
+                7u32,
+            ),
+        );
+    }
+
+    #[test]
+    fn test_mint_with_role() {
+        let s = setup();
+        let minter = grant(&s, &MINTER_ROLE, 0);
+        let alice = Address::generate(&s.env);
+
+        s.token.mint(&minter, &alice, &1000);
+        assert_eq!(
+            s.env.events().all().filter_by_contract(&s.token.address),
+            vec![
+                &s.env,
+                (
+                    s.token.address.clone(),
+                    (symbol_short!("mint"), alice.clone()).into_val(&s.env),
+                    1000i128.into_val(&s.env),
+                ),
+            ]
+        );
+        assert_eq!(s.token.balance(&alice), 1000);
+    }
+
+    #[test]
+    fn test_mint_without_role_fails() {
+        let s = setup();
+        let alice = Address::generate(&s.env);
+
+        let result = s.token.try_mint(&alice, &alice, &1000);
+        assert_eq!(result, Err(Ok(TokenError::NotAuthorized)));
+        assert_eq!(s.token.balance(&alice), 0);
+    }
+
+    #[test]
+    fn test_mint_requires_minter_auth() {
+        let s = setup();
+        let minter = grant(&s, &MINTER_ROLE, 0);
+        let alice = Address::generate(&s.env);
+
+        s.env.set_auths(&[]);
+        assert!(s.token.try_mint(&minter, &alice, &1000).is_err());
+    }
+
+    #[test]
+    fn test_expiry_limited_minter() {
+        let s = setup();
+        let start = 1_000u64;
+        s.env.ledger().with_mut(|li| li.timestamp = start);
+
+        let minter = grant(&s, &MINTER_ROLE, start + 3600);
+        let alice = Address::generate(&s.env);
+        s.token.mint(&minter, &alice, &100);
+
+        // Last second of the grant
+        s.env.ledger().with_mut(|li| li.timestamp = start + 3599);
+        s.token.mint(&minter, &alice, &100);
+
+        // Expired without a revoke
+        s.env.ledger().with_mut(|li| li.timestamp = start + 3600);
+        let result = s.token.try_mint(&minter, &alice, &100);
+        assert_eq!(result, Err(Ok(TokenError::NotAuthorized)));
+        assert_eq!(s.token.balance(&alice), 200);
+
+        // Renewing the grant restores minting
+        s.rbac
+            .grant_role(&s.admin, &MINTER_ROLE, &minter, &(start + 7200));
+        s.token.mint(&minter, &alice, &100);
+        assert_eq!(s.token.balance(&alice), 300);
+    }
+
+    #[test]
+    fn test_revoked_minter_fails() {
+        let s = setup();
+        let minter = grant(&s, &MINTER_ROLE, 0);
+        let alice = Address::generate(&s.env);
+        s.token.mint(&minter, &alice, &100);
+
+        s.rbac.revoke_role(&s.admin, &MINTER_ROLE, &minter);
+
+        let result = s.token.try_mint(&minter, &alice, &100);
+        assert_eq!(result, Err(Ok(TokenError::NotAuthorized)));
+    }
+
+    #[test]
+    fn test_state_changes_extend_instance_ttl() {
+        let s = setup();
+        let minter = grant(&s, &MINTER_ROLE, 0);
+        let alice = Address::generate(&s.env);
+        let bob = Address::generate(&s.env);
+        let instance_ttl = || {
+            s.env
+                .as_contract(&s.token.address, || s.env.storage().instance().get_ttl())
+        };
+        assert_eq!(instance_ttl(), INSTANCE_BUMP_LEDGERS);
+
+        // Let the TTL run down, then a transfer extends it again
+        s.token.mint(&minter, &alice, &100);
+        s.env.ledger().with_mut(|li| li.sequence_number += 1_000);
+        assert_eq!(instance_ttl(), INSTANCE_BUMP_LEDGERS - 1_000);
+        s.token.transfer(&alice, &bob, &10);
+        assert_eq!(instance_ttl(), INSTANCE_BUMP_LEDGERS);
+    }
+
+    #[test]
+    fn test_transfer() {
+        let s = setup();
+        let minter = grant(&s, &MINTER_ROLE, 0);
+        let alice = Address::generate(&s.env);
+        let bob = Address::generate(&s.env);
+        s.token.mint(&minter, &alice, &1000);
+
+        s.token.transfer(&alice, &bob, &300);
+
+        assert_eq!(s.token.balance(&alice), 700);
+        assert_eq!(s.token.balance(&bob), 300);
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #3)")]
+    fn test_transfer_more_than_balance_fails() {
+        let s = setup();
+        let alice = Address::generate(&s.env);
+        let bob = Address::generate(&s.env);
+
+        s.token.transfer(&alice, &bob, &1);
+    }
+
+    #[test]
+    fn test_burn_own_balance() {
+        let s = setup();
+        let minter = grant(&s, &MINTER_ROLE, 0);
+        let alice = Address::generate(&s.env);
+        s.token.mint(&minter, &alice, &1000);
+
+        // Holders burn their own tokens without a role
+        s.token.burn(&alice, &400);
+        assert_eq!(s.token.balance(&alice), 600);
+    }
+
+    #[test]
+    fn test_burn_from_with_role() {
+        let s = setup();
+        let minter = grant(&s, &MINTER_ROLE, 0);
+        let burner = grant(&s, &BURNER_ROLE, 0);
+        let alice = Address::generate(&s.env);
+        s.token.mint(&minter, &alice, &1000);
+        s.token.approve(&alice, &burner, &500, &100);
+
+        s.token.burn_from(&burner, &alice, &300);
+
+        assert_eq!(s.token.balance(&alice), 700);
+        assert_eq!(s.token.allowance(&alice, &burner), 200);
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1)")]
+    fn test_burn_from_without_role_fails() {
+        let s = setup();
+        let minter = grant(&s, &MINTER_ROLE, 0);
+        let alice = Address::generate(&s.env);
+        let spender = Address::generate(&s.env);
+        s.token.mint(&minter, &alice, &1000);
+        s.token.approve(&alice, &spender, &500, &100);
+
+        // An allowance alone is not enough
+        s.token.burn_from(&spender, &alice, &300);
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #4)")]
+    fn test_burn_from_without_allowance_fails() {
+        let s = setup();
+        let minter = grant(&s, &MINTER_ROLE, 0);
+        let burner = grant(&s, &BURNER_ROLE, 0);
+        let alice = Address::generate(&s.env);
+        s.token.mint(&minter, &alice, &1000);
+
+        // The role alone is not enough either
+        s.token.burn_from(&burner, &alice, &300);
+    }
+
+    #[test]
+    fn test_transfer_from_spends_allowance() {
+        let s = setup();
+        let minter = grant(&s, &MINTER_ROLE, 0);
+        let alice = Address::generate(&s.env);
+        let spender = Address::generate(&s.env);
+        let bob = Address::generate(&s.env);
+        s.token.mint(&minter, &alice, &1000);
+        s.token.approve(&alice, &spender, &500, &100);
+
+        s.token.transfer_from(&spender, &alice, &bob, &200);
+
+        assert_eq!(s.token.balance(&bob), 200);
+        assert_eq!(s.token.allowance(&alice, &spender), 300);
+    }
+
+    #[test]
+    fn test_allowance_expires() {
+        let s = setup();
+        let alice = Address::generate(&s.env);
+        let spender = Address::generate(&s.env);
+        s.env.ledger().with_mut(|li| li.sequence_number = 10);
+
+        s.token.approve(&alice, &spender, &500, &20);
+        assert_eq!(s.token.allowance(&alice, &spender), 500);
+
+        s.env.ledger().with_mut(|li| li.sequence_number = 21);
+        assert_eq!(s.token.allowance(&alice, &spender), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #6)")]
+    fn test_approve_past_expiration_fails() {
+        let s = setup();
+        let alice = Address::generate(&s.env);
+        let spender = Address::generate(&s.env);
+        s.env.ledger().with_mut(|li| li.sequence_number = 10);
+
+        s.token.approve(&alice, &spender, &500, &9);
+    }
+
+    #[test]
+    fn test_pause_blocks_mint_and_transfer() {
+        let s = setup();
+        let minter = grant(&s, &MINTER_ROLE, 0);
+        let pauser = grant(&s, &PAUSER_ROLE, 0);
+        let alice = Address::generate(&s.env);
+        let bob = Address::generate(&s.env);
+        s.token.mint(&minter, &alice, &1000);
+
+        s.token.pause(&pauser);
+        assert!(s.token.is_paused());
+
+        let result = s.token.try_mint(&minter, &alice, &100);
+        assert_eq!(result, Err(Ok(TokenError::Paused)));
+        assert!(s.token.try_transfer(&alice, &bob, &100).is_err());
+        assert!(s.token.try_burn(&alice, &100).is_err());
+
+        s.token.unpause(&pauser);
+        s.token.transfer(&alice, &bob, &100);
+        assert_eq!(s.token.balance(&bob), 100);
+    }
+
+    #[test]
+    fn test_pause_without_role_fails() {
+        let s = setup();
+        let minter = grant(&s, &MINTER_ROLE, 0);
+
+        // MINTER does not imply PAUSER
+        let result = s.token.try_pause(&minter);
+        assert_eq!(result, Err(Ok(TokenError::NotAuthorized)));
+        assert!(!s.token.is_paused());
+    }
+
+    #[test]
+    fn test_negative_amounts_fail() {
+        let s = setup();
+        let minter = grant(&s, &MINTER_ROLE, 0);
+        let alice = Address::generate(&s.env);
+
+        let bob = Address::generate(&s.env);
+
+        let result = s.token.try_mint(&minter, &alice, &-1);
+        assert_eq!(result, Err(Ok(TokenError::InvalidAmount)));
+        assert!(s.token.try_transfer(&alice, &bob, &-1).is_err());
+    }
+
+    #[test]
+    fn test_works_with_token_client() {
+        let s = setup();
+        let minter = grant(&s, &MINTER_ROLE, 0);
+        let alice = Address::generate(&s.env);
+        let bob = Address::generate(&s.env);
+        s.token.mint(&minter, &alice, &1000);
+
+        // Any SEP-41 consumer can drive the token through the standard client
+        let client = TokenClient::new(&s.env, &s.token.address);
+        client.transfer(&alice, &bob, &250);
+        assert_eq!(client.balance(&bob), 250);
+        assert_eq!(client.decimals(), 7);
+    }
+}
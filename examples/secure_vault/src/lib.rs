@@ -1,25 +1,45 @@
 //! # SecureVault Example Contract
 //!
 //! This contract demonstrates how to compose the RBAC contract for authorization.
-//! It implements a simple vault that stores tokens and requires the `WITHDRAWER`
-//! role to withdraw funds.
+//! It implements a simple vault that holds a token (any Stellar Asset Contract or
+//! SEP-41 token) and requires the role mapped to `withdraw` in RBAC (normally
+//! `WITHDRAWER`) to withdraw funds.
 //!
 //! ## Usage Flow
 //! 1. Deploy RBAC contract and SecureVault
-//! 2. Initialize SecureVault with RBAC contract address
+//! 2. Initialize SecureVault with RBAC contract and token addresses
 //! 3. Create WITHDRAWER role in RBAC
-//! 4. Grant WITHDRAWER role to authorized accounts
-//! 5. Only accounts with WITHDRAWER role can call `withdraw`
+//! 4. Map `(vault, withdraw)` to WITHDRAWER with `set_function_role`
+//! 5. Grant WITHDRAWER role to authorized accounts
+//! 6. Only accounts with WITHDRAWER role can call `withdraw`
+//!
+//! Re-mapping `withdraw` to another role in RBAC takes effect without redeploying the vault.
+//!
+//! ## Withdrawal Limits
+//! Holders of `VLT_ADMIN` (checked through RBAC) set a `WithdrawalLimit` per role with
+//! `set_withdrawal_limit`: a per-transaction maximum and a cap on what each account can
+//! withdraw in any rolling 24h window. The limit of the role mapped to `withdraw` applies.
+//!
+//! ## Large Withdrawals
+//! Above the approval threshold (set by `VLT_ADMIN`), `withdraw` fails with
+//! `ApprovalRequired`. The withdrawer calls `request_withdrawal` instead, an `APPROVER`
+//! other than the requester calls `approve_withdrawal`, and the requester then calls
+//! `execute_withdrawal`. Either the requester or a `VLT_ADMIN` can cancel a request.
 //!
 //! ## Integration Test Sequence
+//! `tests/integration.rs` runs against a real `RbacContract` and Stellar Asset Contract:
 //! 1. Grant WITHDRAWER role → withdraw succeeds
 //! 2. Revoke WITHDRAWER role → withdraw fails
+//! 3. WITHDRAWER grant expires mid-flow → withdraw fails without a revoke
+//! 4. RBAC address is not an RBAC contract → every role check fails closed
 
 #![no_std]
 
 use soroban_sdk::{
-    contract, contracterror, contractimpl, contracttype, symbol_short, Address, Env, Symbol,
+    contract, contracterror, contractevent, contractimpl, contracttype, symbol_short, token,
+    Address, Env, Symbol, Vec,
 };
+use stellar_keystone_rbac_client::{RbacClient, DEFAULT_ADMIN_ROLE};
 
 // =============================================================================
 // Constants
@@ -28,6 +48,26 @@ use soroban_sdk::{
 /// The role required to withdraw from the vault
 const WITHDRAWER_ROLE: Symbol = symbol_short!("WITHDRAW");
 
+/// The role required to configure the vault
+const VAULT_ADMIN_ROLE: Symbol = symbol_short!("VLT_ADMIN");
+
+/// The role required to approve withdrawals above the approval threshold
+const APPROVER_ROLE: Symbol = symbol_short!("APPROVER");
+
+/// Length of the rolling spend window in seconds
+const SPEND_WINDOW: u64 = 24 * 60 * 60;
+
+/// Spend records are garbage-collected after this many ledgers. The window itself is
+/// checked against ledger timestamps; the TTL assumes no more than one ledger per second,
+/// so a record cannot expire while it still counts.
+const SPEND_TTL_LEDGERS: u32 = SPEND_WINDOW as u32;
+
+/// Most spend records kept per account. Beyond this the two oldest are merged.
+const MAX_SPENDS: u32 = 16;
+
+/// Pending withdrawal requests are kept alive for about 30 days at 5s per ledger
+const REQUEST_BUMP_LEDGERS: u32 = 518_400;
+
 // =============================================================================
 // Storage Keys
 // =============================================================================
@@ -35,12 +75,69 @@ const WITHDRAWER_ROLE: Symbol = symbol_short!("WITHDRAW");
 #[contracttype]
 #[derive(Clone)]
 pub enum DataKey {
+    /// The address allowed to initialize the vault
+    Deployer,
     /// The RBAC contract address
     RbacAddress,
-    /// The vault balance
-    Balance,
+    /// The token held by the vault
+    Token,
     /// Whether the vault is initialized
     Initialized,
+    /// Maps role to its `WithdrawalLimit` (persistent, absent = unlimited)
+    Limit(Symbol),
+    /// Maps account to its `Vec<Spend>` within the current window (temporary)
+    Spent(Address),
+    /// Amount above which withdrawals need approval (i128, absent = never)
+    ApprovalThreshold,
+    /// Id of the next withdrawal request (u64)
+    NextRequestId,
+    /// Maps request id to its pending `WithdrawalRequest` (persistent)
+    Request(u64),
+}
+
+/// Withdrawal limits for holders of a role.
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct WithdrawalLimit {
+    /// Maximum amount per withdrawal (0 = unlimited)
+    pub per_tx: i128,
+    /// Maximum amount per account in any rolling 24h window (0 = unlimited)
+    pub per_window: i128,
+}
+
+/// A withdrawal above the approval threshold, waiting for approval or execution.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct WithdrawalRequest {
+    /// Account that requested the withdrawal and receives the funds
+    pub requester: Address,
+    /// Amount to withdraw
+    pub amount: i128,
+    /// APPROVER holder that approved the request, if any
+    pub approver: Option<Address>,
+}
+
+/// Event emitted when the vault is pointed at another RBAC contract.
+#[contractevent(topics = ["RbacRebnd"], data_format = "vec")]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RbacRebound {
+    /// RBAC contract the vault used before
+    #[topic]
+    pub old_rbac: Address,
+    /// RBAC contract the vault uses from now on
+    pub new_rbac: Address,
+    /// VLT_ADMIN holder that made the change
+    pub caller: Address,
+}
+
+/// A past withdrawal counted against the rolling window.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Spend {
+    /// Ledger timestamp of the withdrawal
+    pub timestamp: u64,
+    /// Amount withdrawn
+    pub amount: i128,
 }
 
 // =============================================================================
@@ -61,21 +158,22 @@ pub enum VaultError {
     NotInitialized = 4,
     /// Invalid amount
     InvalidAmount = 5,
-}
-
-// =============================================================================
-// RBAC Contract Client Interface
-// =============================================================================
-
-// Define the interface for calling the RBAC contract
-mod rbac_client {
-    use soroban_sdk::{contractclient, Address, Env, Symbol};
-
-    #[contractclient(name = "RbacClient")]
-    pub trait RbacContract {
-        fn has_role(env: Env, role: Symbol, account: Address) -> bool;
-        fn require_role(env: Env, role: Symbol, account: Address);
-    }
+    /// Withdrawal exceeds the per-transaction or rolling window limit
+    LimitExceeded = 6,
+    /// Amount is above the approval threshold; use `request_withdrawal`
+    ApprovalRequired = 7,
+    /// No pending withdrawal request with this id
+    RequestNotFound = 8,
+    /// Requesters cannot approve their own withdrawal
+    SelfApproval = 9,
+    /// Withdrawal request has not been approved
+    NotApproved = 10,
+    /// Withdrawal request was already approved
+    AlreadyApproved = 11,
+    /// Address does not answer the RBAC interface
+    InvalidRbacContract = 12,
+    /// Amount is at or below the approval threshold; use `withdraw`
+    ApprovalNotRequired = 13,
 }
 
 // =============================================================================
@@ -87,17 +185,36 @@ pub struct SecureVaultContract;
 
 #[contractimpl]
 impl SecureVaultContract {
-    /// Initialize the vault with an RBAC contract address.
+    /// Record the deployer, the only address allowed to call `initialize`.
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    /// * `deployer` - The address that will initialize the vault
+    ///
+    /// # Note
+    /// Constructor arguments are fixed in the deploy transaction, so the
+    /// initialization cannot be front-run.
+    pub fn __constructor(env: Env, deployer: Address) {
+        env.storage().instance().set(&DataKey::Deployer, &deployer);
+    }
+
+    /// Initialize the vault with an RBAC contract and the token it holds.
     ///
     /// # Arguments
     /// * `env` - The Soroban environment
     /// * `rbac_address` - The address of the deployed RBAC contract
-    /// * `initial_balance` - Initial vault balance (for demo purposes)
-    pub fn initialize(
-        env: Env,
-        rbac_address: Address,
-        initial_balance: i128,
-    ) -> Result<(), VaultError> {
+    /// * `token` - The token contract, e.g. a Stellar Asset Contract
+    ///
+    /// # Authorization
+    /// The deployer must authorize.
+    pub fn initialize(env: Env, rbac_address: Address, token: Address) -> Result<(), VaultError> {
+        let deployer: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Deployer)
+            .ok_or(VaultError::NotInitialized)?;
+        deployer.require_auth();
+
         // Check not already initialized
         if env.storage().instance().has(&DataKey::Initialized) {
             return Err(VaultError::AlreadyInitialized);
@@ -108,10 +225,8 @@ impl SecureVaultContract {
             .instance()
             .set(&DataKey::RbacAddress, &rbac_address);
 
-        // Set initial balance
-        env.storage()
-            .instance()
-            .set(&DataKey::Balance, &initial_balance);
+        // Store token address
+        env.storage().instance().set(&DataKey::Token, &token);
 
         // Mark as initialized
         env.storage().instance().set(&DataKey::Initialized, &true);
@@ -119,36 +234,30 @@ impl SecureVaultContract {
         Ok(())
     }
 
-    /// Deposit funds into the vault.
+    /// Deposit tokens into the vault.
     ///
     /// # Arguments
     /// * `env` - The Soroban environment
+    /// * `from` - The address the tokens are transferred from
     /// * `amount` - Amount to deposit
     ///
-    /// # Note
-    /// In a real implementation, this would transfer tokens from the caller.
-    /// For this demo, we simply add to the balance.
-    pub fn deposit(env: Env, amount: i128) -> Result<(), VaultError> {
+    /// # Authorization
+    /// `from` must authorize. Anyone can deposit.
+    pub fn deposit(env: Env, from: Address, amount: i128) -> Result<(), VaultError> {
+        from.require_auth();
         Self::ensure_initialized(&env)?;
 
         if amount <= 0 {
             return Err(VaultError::InvalidAmount);
         }
 
-        let current_balance: i128 = env
-            .storage()
-            .instance()
-            .get(&DataKey::Balance)
-            .unwrap_or(0);
-
-        env.storage()
-            .instance()
-            .set(&DataKey::Balance, &(current_balance + amount));
+        let this = env.current_contract_address();
+        Self::token(&env)?.transfer(&from, &this, &amount);
 
         Ok(())
     }
 
-    /// Withdraw funds from the vault.
+    /// Withdraw tokens from the vault to the caller.
     ///
     /// # Arguments
     /// * `env` - The Soroban environment
@@ -156,78 +265,512 @@ impl SecureVaultContract {
     /// * `amount` - Amount to withdraw
     ///
     /// # Authorization
-    /// Caller must have the WITHDRAWER role in the RBAC contract.
+    /// Caller must authorize and have the role RBAC maps to `(vault, withdraw)`.
+    /// Both are checked by the RBAC contract's `check_call`. The vault also requires
+    /// the caller's auth itself, so the signature covers `amount` and the RBAC check
+    /// runs as a signed sub-invocation.
+    ///
+    /// A contract `caller` passes `require_auth` only when it invokes `withdraw`
+    /// directly, and must pre-authorize the nested RBAC check
+    /// (`stellar_keystone_rbac_client::authorize_check_call`).
     ///
-    /// # Demo Note
-    /// In a real implementation, this would:
-    /// 1. Check caller.require_auth()
-    /// 2. Transfer tokens to the caller
-    /// For this demo, we check RBAC and update balance.
+    /// # Errors
+    /// - `ApprovalRequired` if `amount` is above the approval threshold
+    /// - `LimitExceeded` if `amount` exceeds the mapped role's per-transaction limit, or
+    ///   would take the caller past the role's limit for the last 24h
     pub fn withdraw(env: Env, caller: Address, amount: i128) -> Result<(), VaultError> {
+        caller.require_auth();
         Self::ensure_initialized(&env)?;
 
         if amount <= 0 {
             return Err(VaultError::InvalidAmount);
         }
 
-        // Get RBAC contract address
-        let rbac_address: Address = env
+        // Require the caller's signature and the role mapped to this function
+        let role = Self::require_withdrawer(&env, &caller)?;
+
+        // Large withdrawals go through request_withdrawal
+        let threshold = Self::get_approval_threshold(env.clone());
+        if threshold != 0 && amount > threshold {
+            return Err(VaultError::ApprovalRequired);
+        }
+
+        Self::pay_out(&env, &role, &caller, amount, true)
+    }
+
+    /// Request a withdrawal above the approval threshold.
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    /// * `caller` - The address requesting withdrawal (must hold the `withdraw` role)
+    /// * `amount` - Amount to withdraw
+    ///
+    /// # Returns
+    /// The id of the pending request.
+    ///
+    /// # Authorization
+    /// Same as `withdraw`.
+    ///
+    /// # Errors
+    /// - `ApprovalNotRequired` if `amount` is at or below the approval threshold, or no
+    ///   threshold is set
+    ///
+    /// # Note
+    /// The request needs `approve_withdrawal` by an APPROVER other than the caller,
+    /// then `execute_withdrawal` by the caller.
+    pub fn request_withdrawal(env: Env, caller: Address, amount: i128) -> Result<u64, VaultError> {
+        caller.require_auth();
+        Self::ensure_initialized(&env)?;
+
+        if amount <= 0 {
+            return Err(VaultError::InvalidAmount);
+        }
+        Self::require_withdrawer(&env, &caller)?;
+
+        // Smaller withdrawals go through withdraw, where the per-transaction limit applies
+        let threshold = Self::get_approval_threshold(env.clone());
+        if threshold == 0 || amount <= threshold {
+            return Err(VaultError::ApprovalNotRequired);
+        }
+
+        let id: u64 = env
             .storage()
             .instance()
-            .get(&DataKey::RbacAddress)
-            .ok_or(VaultError::NotInitialized)?;
+            .get(&DataKey::NextRequestId)
+            .unwrap_or(0);
+        env.storage()
+            .instance()
+            .set(&DataKey::NextRequestId, &(id + 1));
 
-        // Check authorization via RBAC
-        // Create client for RBAC contract
-        let rbac_client = rbac_client::RbacClient::new(&env, &rbac_address);
+        let request = WithdrawalRequest {
+            requester: caller,
+            amount,
+            approver: None,
+        };
+        Self::save_request(&env, id, &request);
+        Ok(id)
+    }
 
-        // Check if caller has WITHDRAWER role
-        let has_role = rbac_client.has_role(&WITHDRAWER_ROLE, &caller);
-        if !has_role {
+    /// Approve a pending withdrawal request.
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    /// * `approver` - The approving address (must hold APPROVER in RBAC)
+    /// * `id` - The request id
+    ///
+    /// # Authorization
+    /// Approver must authorize and hold APPROVER in the vault's RBAC contract.
+    ///
+    /// # Errors
+    /// - `RequestNotFound` if there is no pending request with this id
+    /// - `NotAuthorized` if the approver lacks APPROVER
+    /// - `SelfApproval` if the approver made the request
+    /// - `AlreadyApproved` if the request was already approved
+    pub fn approve_withdrawal(env: Env, approver: Address, id: u64) -> Result<(), VaultError> {
+        approver.require_auth();
+        let mut request = Self::get_request(env.clone(), id).ok_or(VaultError::RequestNotFound)?;
+
+        if Self::rbac(&env)?
+            .try_authorize(&APPROVER_ROLE, &approver)
+            .is_err()
+        {
             return Err(VaultError::NotAuthorized);
         }
+        if approver == request.requester {
+            return Err(VaultError::SelfApproval);
+        }
+        if request.approver.is_some() {
+            return Err(VaultError::AlreadyApproved);
+        }
 
-        // Check balance
-        let current_balance: i128 = env
-            .storage()
+        request.approver = Some(approver);
+        Self::save_request(&env, id, &request);
+        Ok(())
+    }
+
+    /// Execute an approved withdrawal request.
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    /// * `caller` - The address that made the request
+    /// * `id` - The request id
+    ///
+    /// # Authorization
+    /// Caller must be the requester, authorize, and still hold the `withdraw` role.
+    ///
+    /// # Errors
+    /// - `RequestNotFound` if there is no pending request with this id
+    /// - `NotAuthorized` if the caller is not the requester or lost the role
+    /// - `NotApproved` if the request has not been approved, or its approver no longer
+    ///   holds APPROVER
+    /// - `LimitExceeded` if the request would exceed the rolling window limit
+    ///
+    /// # Note
+    /// Approval replaces the per-transaction limit; the rolling window still applies.
+    pub fn execute_withdrawal(env: Env, caller: Address, id: u64) -> Result<(), VaultError> {
+        caller.require_auth();
+        let request = Self::get_request(env.clone(), id).ok_or(VaultError::RequestNotFound)?;
+
+        if caller != request.requester {
+            return Err(VaultError::NotAuthorized);
+        }
+        // An approval only counts while the approver still holds APPROVER
+        let approver = request.approver.ok_or(VaultError::NotApproved)?;
+        if !Self::rbac(&env)?.has_role(&APPROVER_ROLE, &approver) {
+            return Err(VaultError::NotApproved);
+        }
+        let role = Self::require_withdrawer(&env, &caller)?;
+
+        Self::pay_out(&env, &role, &caller, request.amount, false)?;
+        env.storage().persistent().remove(&DataKey::Request(id));
+        Ok(())
+    }
+
+    /// Cancel a pending withdrawal request.
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    /// * `caller` - The requester, or a VLT_ADMIN holder
+    /// * `id` - The request id
+    ///
+    /// # Authorization
+    /// Caller must authorize and be the requester or hold VLT_ADMIN in RBAC.
+    ///
+    /// # Errors
+    /// - `RequestNotFound` if there is no pending request with this id
+    /// - `NotAuthorized` otherwise
+    pub fn cancel_withdrawal(env: Env, caller: Address, id: u64) -> Result<(), VaultError> {
+        caller.require_auth();
+        let request = Self::get_request(env.clone(), id).ok_or(VaultError::RequestNotFound)?;
+
+        if caller != request.requester
+            && Self::rbac(&env)?
+                .try_authorize(&VAULT_ADMIN_ROLE, &caller)
+                .is_err()
+        {
+            return Err(VaultError::NotAuthorized);
+        }
+
+        env.storage().persistent().remove(&DataKey::Request(id));
+        Ok(())
+    }
+
+    /// Set the amount above which withdrawals need approval.
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    /// * `caller` - The address invoking this function (must have VLT_ADMIN in RBAC)
+    /// * `threshold` - Largest amount `withdraw` accepts (0 = no approvals needed)
+    ///
+    /// # Authorization
+    /// Caller must authorize and hold VLT_ADMIN in the vault's RBAC contract.
+    ///
+    /// # Errors
+    /// - `NotAuthorized` if the caller lacks VLT_ADMIN
+    /// - `InvalidAmount` if threshold is negative
+    pub fn set_approval_threshold(
+        env: Env,
+        caller: Address,
+        threshold: i128,
+    ) -> Result<(), VaultError> {
+        caller.require_auth();
+        Self::ensure_initialized(&env)?;
+
+        if Self::rbac(&env)?
+            .try_authorize(&VAULT_ADMIN_ROLE, &caller)
+            .is_err()
+        {
+            return Err(VaultError::NotAuthorized);
+        }
+
+        if threshold < 0 {
+            return Err(VaultError::InvalidAmount);
+        }
+
+        env.storage()
             .instance()
-            .get(&DataKey::Balance)
-            .unwrap_or(0);
+            .set(&DataKey::ApprovalThreshold, &threshold);
+        Ok(())
+    }
 
-        if current_balance < amount {
-            return Err(VaultError::InsufficientBalance);
+    /// Move the vault to another RBAC contract, e.g. after upgrading the RBAC deployment.
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    /// * `caller` - The address invoking this function (must have VLT_ADMIN in the current RBAC)
+    /// * `new_rbac` - The address of the new RBAC contract
+    ///
+    /// # Authorization
+    /// Caller must authorize and hold VLT_ADMIN in the *current* RBAC contract.
+    ///
+    /// # Errors
+    /// - `NotAuthorized` if the caller lacks VLT_ADMIN
+    /// - `InvalidRbacContract` if `new_rbac` does not answer `default_admin_role`
+    ///
+    /// # Note
+    /// Emits `RbacRebnd` with the old and new addresses. Roles, the `withdraw` mapping
+    /// and VLT_ADMIN must be set up in the new contract first, or the vault locks up.
+    pub fn set_rbac_address(
+        env: Env,
+        caller: Address,
+        new_rbac: Address,
+    ) -> Result<(), VaultError> {
+        caller.require_auth();
+        Self::ensure_initialized(&env)?;
+
+        let rbac_client = Self::rbac(&env)?;
+        if rbac_client
+            .try_authorize(&VAULT_ADMIN_ROLE, &caller)
+            .is_err()
+        {
+            return Err(VaultError::NotAuthorized);
+        }
+
+        // The new contract must speak the RBAC interface
+        match RbacClient::new(&env, &new_rbac).try_default_admin_role() {
+            Ok(Ok(role)) if role == DEFAULT_ADMIN_ROLE => {}
+            _ => return Err(VaultError::InvalidRbacContract),
         }
 
-        // Update balance
         env.storage()
             .instance()
-            .set(&DataKey::Balance, &(current_balance - amount));
+            .set(&DataKey::RbacAddress, &new_rbac);
+        RbacRebound {
+            old_rbac: rbac_client.address,
+            new_rbac,
+            caller,
+        }
+        .publish(&env);
+        Ok(())
+    }
+
+    /// Set the withdrawal limits for holders of a role.
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    /// * `caller` - The address invoking this function (must have VLT_ADMIN in RBAC)
+    /// * `role` - The role the limits apply to
+    /// * `limit` - Per-transaction and rolling 24h limits (0 = unlimited)
+    ///
+    /// # Authorization
+    /// Caller must authorize and hold VLT_ADMIN in the vault's RBAC contract.
+    ///
+    /// # Errors
+    /// - `NotAuthorized` if the caller lacks VLT_ADMIN
+    /// - `InvalidAmount` if either limit is negative
+    pub fn set_withdrawal_limit(
+        env: Env,
+        caller: Address,
+        role: Symbol,
+        limit: WithdrawalLimit,
+    ) -> Result<(), VaultError> {
+        caller.require_auth();
+        Self::ensure_initialized(&env)?;
+
+        if Self::rbac(&env)?
+            .try_authorize(&VAULT_ADMIN_ROLE, &caller)
+            .is_err()
+        {
+            return Err(VaultError::NotAuthorized);
+        }
 
+        if limit.per_tx < 0 || limit.per_window < 0 {
+            return Err(VaultError::InvalidAmount);
+        }
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::Limit(role), &limit);
         Ok(())
     }
 
-    /// Get the current vault balance.
-    pub fn get_balance(env: Env) -> i128 {
+    /// Get the amount above which withdrawals need approval (0 = never).
+    pub fn get_approval_threshold(env: Env) -> i128 {
         env.storage()
             .instance()
-            .get(&DataKey::Balance)
+            .get(&DataKey::ApprovalThreshold)
             .unwrap_or(0)
     }
 
+    /// Get a pending withdrawal request.
+    pub fn get_request(env: Env, id: u64) -> Option<WithdrawalRequest> {
+        env.storage().persistent().get(&DataKey::Request(id))
+    }
+
+    /// Get the withdrawal limits for holders of a role (unlimited if unset).
+    pub fn get_withdrawal_limit(env: Env, role: Symbol) -> WithdrawalLimit {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Limit(role))
+            .unwrap_or_default()
+    }
+
+    /// Get the amount an account has withdrawn in the last 24h.
+    pub fn get_spent(env: Env, account: Address) -> i128 {
+        Self::recent_spends(&env, &account)
+            .iter()
+            .map(|spend| spend.amount)
+            .sum()
+    }
+
+    /// Get the vault's token balance (0 if not initialized).
+    pub fn get_balance(env: Env) -> i128 {
+        match Self::token(&env) {
+            Ok(token) => token.balance(&env.current_contract_address()),
+            Err(_) => 0,
+        }
+    }
+
+    /// Get the token held by the vault.
+    pub fn get_token(env: Env) -> Option<Address> {
+        env.storage().instance().get(&DataKey::Token)
+    }
+
+    /// Get the address allowed to initialize the vault.
+    pub fn get_deployer(env: Env) -> Option<Address> {
+        env.storage().instance().get(&DataKey::Deployer)
+    }
+
     /// Get the RBAC contract address.
     pub fn get_rbac_address(env: Env) -> Option<Address> {
         env.storage().instance().get(&DataKey::RbacAddress)
     }
 
-    /// Get the WITHDRAWER role symbol.
+    /// Get the WITHDRAWER role symbol, the role `withdraw` is normally mapped to.
     pub fn withdrawer_role(_env: Env) -> Symbol {
         WITHDRAWER_ROLE
     }
 
+    /// Get the VLT_ADMIN role symbol, the role required to configure the vault.
+    pub fn vault_admin_role(_env: Env) -> Symbol {
+        VAULT_ADMIN_ROLE
+    }
+
+    /// Get the APPROVER role symbol, the role required to approve large withdrawals.
+    pub fn approver_role(_env: Env) -> Symbol {
+        APPROVER_ROLE
+    }
+
     // =========================================================================
     // Internal Helpers
     // =========================================================================
 
+    fn rbac(env: &Env) -> Result<RbacClient<'_>, VaultError> {
+        let rbac_address: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::RbacAddress)
+            .ok_or(VaultError::NotInitialized)?;
+        Ok(RbacClient::new(env, &rbac_address))
+    }
+
+    /// Store a pending request and keep it alive for `REQUEST_BUMP_LEDGERS`.
+    fn save_request(env: &Env, id: u64, request: &WithdrawalRequest) {
+        let key = DataKey::Request(id);
+        env.storage().persistent().set(&key, request);
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, REQUEST_BUMP_LEDGERS, REQUEST_BUMP_LEDGERS);
+    }
+
+    /// Check the caller's signature and the role RBAC maps to `(vault, withdraw)`.
+    ///
+    /// # Returns
+    /// The mapped role, whose limits apply to the caller.
+    fn require_withdrawer(env: &Env, caller: &Address) -> Result<Symbol, VaultError> {
+        let rbac_client = Self::rbac(env)?;
+        let this = env.current_contract_address();
+        let withdraw_fn = symbol_short!("withdraw");
+        if rbac_client
+            .try_check_call(&this, &withdraw_fn, caller)
+            .is_err()
+        {
+            return Err(VaultError::NotAuthorized);
+        }
+        rbac_client
+            .get_function_role(&this, &withdraw_fn)
+            .ok_or(VaultError::NotAuthorized)
+    }
+
+    /// Enforce `role`'s limits, record the spend and transfer `amount` to `caller`.
+    fn pay_out(
+        env: &Env,
+        role: &Symbol,
+        caller: &Address,
+        amount: i128,
+        enforce_per_tx: bool,
+    ) -> Result<(), VaultError> {
+        let limit = Self::get_withdrawal_limit(env.clone(), role.clone());
+        if enforce_per_tx && limit.per_tx != 0 && amount > limit.per_tx {
+            return Err(VaultError::LimitExceeded);
+        }
+        let mut spends = Self::recent_spends(env, caller);
+        let spent: i128 = spends.iter().map(|spend| spend.amount).sum();
+        if limit.per_window != 0 && spent + amount > limit.per_window {
+            return Err(VaultError::LimitExceeded);
+        }
+
+        // Check balance
+        let this = env.current_contract_address();
+        let token = Self::token(env)?;
+        if token.balance(&this) < amount {
+            return Err(VaultError::InsufficientBalance);
+        }
+
+        // Record the spend for the rolling window. Merged records keep the later
+        // timestamp, so the older amount counts slightly longer instead of dropping out.
+        if spends.len() >= MAX_SPENDS {
+            let oldest = spends.pop_front_unchecked();
+            let next = spends.get_unchecked(0);
+            spends.set(
+                0,
+                Spend {
+                    timestamp: next.timestamp,
+                    amount: oldest.amount + next.amount,
+                },
+            );
+        }
+        spends.push_back(Spend {
+            timestamp: env.ledger().timestamp(),
+            amount,
+        });
+        let spent_key = DataKey::Spent(caller.clone());
+        env.storage().temporary().set(&spent_key, &spends);
+        env.storage()
+            .temporary()
+            .extend_ttl(&spent_key, SPEND_TTL_LEDGERS, SPEND_TTL_LEDGERS);
+
+        // Transfer to the caller
+        token.transfer(&this, caller, &amount);
+        Ok(())
+    }
+
+    /// Withdrawals by `account` within the last `SPEND_WINDOW` seconds.
+    fn recent_spends(env: &Env, account: &Address) -> Vec<Spend> {
+        let now = env.ledger().timestamp();
+        let spends: Vec<Spend> = env
+            .storage()
+            .temporary()
+            .get(&DataKey::Spent(account.clone()))
+            .unwrap_or(Vec::new(env));
+        let mut recent = Vec::new(env);
+        for spend in spends.iter() {
+            if spend.timestamp + SPEND_WINDOW > now {
+                recent.push_back(spend);
+            }
+        }
+        recent
+    }
+
+    fn token(env: &Env) -> Result<token::Client<'_>, VaultError> {
+        let token: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Token)
+            .ok_or(VaultError::NotInitialized)?;
+        Ok(token::Client::new(env, &token))
+    }
+
     fn ensure_initialized(env: &Env) -> Result<(), VaultError> {
         if !env
             .storage()
@@ -248,32 +791,48 @@ impl SecureVaultContract {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use soroban_sdk::testutils::Address as _;
-    use soroban_sdk::Env;
+    use soroban_sdk::testutils::storage::Persistent as _;
+    use soroban_sdk::testutils::{Address as _, Events, Ledger};
+    use soroban_sdk::token::{StellarAssetClient, TokenClient};
+    use soroban_sdk::{vec, Env, IntoVal};
+    use stellar_keystone_rbac::{RbacContract, RbacContractClient};
+
+    // These tests cover the vault's own logic. End-to-end flows (grant -> withdraw ->
+    // revoke -> fail, role expiry, a non-RBAC address) are in tests/integration.rs,
+    // and contract callers in tests/keeper.rs.
+
+    /// Register a Stellar Asset Contract and return its address.
+    fn create_token(env: &Env) -> Address {
+        let issuer = Address::generate(env);
+        env.register_stellar_asset_contract_v2(issuer).address()
+    }
 
-    // For integration tests with RBAC, we would need to:
-    // 1. Deploy RBAC contract
-    // 2. Deploy SecureVault with RBAC address
-    // 3. Test the grant -> withdraw -> revoke -> fail sequence
-    //
-    // These tests demonstrate the basic vault functionality.
-    // Full integration tests are in the tests/ directory.
+    /// Register a vault holding `balance` tokens, with a random RBAC address.
+    fn setup_vault(env: &Env, balance: i128) -> (Address, SecureVaultContractClient<'static>) {
+        let token = create_token(env);
+        let contract_id = env.register(SecureVaultContract, (Address::generate(env),));
+        let client = SecureVaultContractClient::new(env, &contract_id);
+        client.initialize(&Address::generate(env), &token);
+        StellarAssetClient::new(env, &token).mint(&contract_id, &balance);
+        (token, client)
+    }
 
     #[test]
     fn test_initialize() {
         let env = Env::default();
         env.mock_all_auths();
 
-        let contract_id = env.register(SecureVaultContract, ());
+        let contract_id = env.register(SecureVaultContract, (Address::generate(&env),));
         let client = SecureVaultContractClient::new(&env, &contract_id);
+        assert_eq!(client.get_balance(), 0);
 
         let rbac_address = Address::generate(&env);
-        let initial_balance = 1000i128;
+        let token = create_token(&env);
+        client.initialize(&rbac_address, &token);
 
-        client.initialize(&rbac_address, &initial_balance);
-
-        assert_eq!(client.get_balance(), initial_balance);
+        assert_eq!(client.get_balance(), 0);
         assert_eq!(client.get_rbac_address(), Some(rbac_address));
+        assert_eq!(client.get_token(), Some(token));
     }
 
     #[test]
@@ -281,14 +840,47 @@ mod tests {
         let env = Env::default();
         env.mock_all_auths();
 
-        let contract_id = env.register(SecureVaultContract, ());
+        let (token, client) = setup_vault(&env, 1000);
+        let depositor = Address::generate(&env);
+        StellarAssetClient::new(&env, &token).mint(&depositor, &500);
+
+        client.deposit(&depositor, &500);
+        assert_eq!(client.get_balance(), 1500);
+        assert_eq!(TokenClient::new(&env, &token).balance(&depositor), 0);
+    }
+
+    #[test]
+    fn test_deposit_requires_depositor_auth() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let (token, client) = setup_vault(&env, 1000);
+        let depositor = Address::generate(&env);
+        StellarAssetClient::new(&env, &token).mint(&depositor, &500);
+
+        env.set_auths(&[]);
+        assert!(client.try_deposit(&depositor, &500).is_err());
+        assert_eq!(TokenClient::new(&env, &token).balance(&depositor), 500);
+    }
+
+    #[test]
+    fn test_initialize_requires_deployer_auth() {
+        let env = Env::default();
+
+        let deployer = Address::generate(&env);
+        let contract_id = env.register(SecureVaultContract, (deployer.clone(),));
         let client = SecureVaultContractClient::new(&env, &contract_id);
+        assert_eq!(client.get_deployer(), Some(deployer.clone()));
 
+        // Without the deployer's signature nobody can claim the vault
         let rbac_address = Address::generate(&env);
-        client.initialize(&rbac_address, &1000);
+        let token = create_token(&env);
+        assert!(client.try_initialize(&rbac_address, &token).is_err());
+        assert_eq!(client.get_rbac_address(), None);
 
-        client.deposit(&500);
-        assert_eq!(client.get_balance(), 1500);
+        env.mock_all_auths();
+        client.initialize(&rbac_address, &token);
+        assert_eq!(env.auths()[0].0, deployer);
     }
 
     #[test]
@@ -296,14 +888,15 @@ mod tests {
         let env = Env::default();
         env.mock_all_auths();
 
-        let contract_id = env.register(SecureVaultContract, ());
+        let contract_id = env.register(SecureVaultContract, (Address::generate(&env),));
         let client = SecureVaultContractClient::new(&env, &contract_id);
 
         let rbac_address = Address::generate(&env);
-        client.initialize(&rbac_address, &1000);
+        let token = create_token(&env);
+        client.initialize(&rbac_address, &token);
 
         // Second initialization should fail
-        let result = client.try_initialize(&rbac_address, &500);
+        let result = client.try_initialize(&rbac_address, &create_token(&env));
         assert!(result.is_err());
     }
 
@@ -312,31 +905,550 @@ mod tests {
         let env = Env::default();
         env.mock_all_auths();
 
-        let contract_id = env.register(SecureVaultContract, ());
-        let client = SecureVaultContractClient::new(&env, &contract_id);
-
-        let rbac_address = Address::generate(&env);
-        client.initialize(&rbac_address, &1000);
+        let (_token, client) = setup_vault(&env, 1000);
+        let depositor = Address::generate(&env);
 
         // Zero amount should fail
-        let result = client.try_deposit(&0);
+        let result = client.try_deposit(&depositor, &0);
         assert!(result.is_err());
 
         // Negative amount should fail
-        let result = client.try_deposit(&-100);
+        let result = client.try_deposit(&depositor, &-100);
         assert!(result.is_err());
     }
 
     #[test]
     fn test_withdrawer_role_symbol() {
         let env = Env::default();
-        let contract_id = env.register(SecureVaultContract, ());
+        let contract_id = env.register(SecureVaultContract, (Address::generate(&env),));
         let client = SecureVaultContractClient::new(&env, &contract_id);
 
         let role = client.withdrawer_role();
         assert_eq!(role, symbol_short!("WITHDRAW"));
     }
 
-    // Integration test demonstrating the full flow would go here
-    // See examples/secure_vault/tests/integration.rs for the complete test
+    fn setup_with_rbac() -> (
+        Env,
+        Address,
+        RbacContractClient<'static>,
+        SecureVaultContractClient<'static>,
+    ) {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let rbac_id = env.register(RbacContract, ());
+        let rbac = RbacContractClient::new(&env, &rbac_id);
+        let admin = Address::generate(&env);
+        rbac.initialize(&admin);
+        rbac.create_role(&admin, &WITHDRAWER_ROLE, &rbac.default_admin_role());
+        rbac.create_role(&admin, &VAULT_ADMIN_ROLE, &rbac.default_admin_role());
+        rbac.grant_role(&admin, &VAULT_ADMIN_ROLE, &admin, &0);
+        rbac.create_role(&admin, &APPROVER_ROLE, &rbac.default_admin_role());
+
+        let token = create_token(&env);
+        let vault_id = env.register(SecureVaultContract, (Address::generate(&env),));
+        let vault = SecureVaultContractClient::new(&env, &vault_id);
+        vault.initialize(&rbac_id, &token);
+        StellarAssetClient::new(&env, &token).mint(&vault_id, &1000);
+        rbac.set_function_role(
+            &admin,
+            &vault_id,
+            &symbol_short!("withdraw"),
+            &WITHDRAWER_ROLE,
+        );
+
+        (env, admin, rbac, vault)
+    }
+
+    fn token_balance(env: &Env, vault: &SecureVaultContractClient, account: &Address) -> i128 {
+        TokenClient::new(env, &vault.get_token().unwrap()).balance(account)
+    }
+
+    #[test]
+    fn test_withdraw_with_role() {
+        let (env, admin, rbac, vault) = setup_with_rbac();
+
+        let withdrawer = Address::generate(&env);
+        rbac.grant_role(&admin, &WITHDRAWER_ROLE, &withdrawer, &0);
+
+        vault.withdraw(&withdrawer, &400);
+        assert_eq!(vault.get_balance(), 600);
+        assert_eq!(token_balance(&env, &vault, &withdrawer), 400);
+    }
+
+    #[test]
+    fn test_withdraw_more_than_balance_fails() {
+        let (env, admin, rbac, vault) = setup_with_rbac();
+
+        let withdrawer = Address::generate(&env);
+        rbac.grant_role(&admin, &WITHDRAWER_ROLE, &withdrawer, &0);
+
+        let result = vault.try_withdraw(&withdrawer, &1001);
+        assert_eq!(result, Err(Ok(VaultError::InsufficientBalance)));
+        assert_eq!(vault.get_balance(), 1000);
+    }
+
+    #[test]
+    fn test_withdraw_without_role_fails() {
+        let (env, _admin, _rbac, vault) = setup_with_rbac();
+
+        let outsider = Address::generate(&env);
+        let result = vault.try_withdraw(&outsider, &400);
+        assert_eq!(result, Err(Ok(VaultError::NotAuthorized)));
+        assert_eq!(vault.get_balance(), 1000);
+        assert_eq!(token_balance(&env, &vault, &outsider), 0);
+    }
+
+    #[test]
+    fn test_unsigned_withdraw_rejected() {
+        let (env, admin, rbac, vault) = setup_with_rbac();
+
+        let withdrawer = Address::generate(&env);
+        rbac.grant_role(&admin, &WITHDRAWER_ROLE, &withdrawer, &0);
+
+        // Anyone can name a WITHDRAWER's address; without their signature the call fails
+        env.set_auths(&[]);
+        assert!(vault.try_withdraw(&withdrawer, &400).is_err());
+        assert_eq!(vault.get_balance(), 1000);
+    }
+
+    #[test]
+    fn test_withdraw_remapped_without_redeploy() {
+        let (env, admin, rbac, vault) = setup_with_rbac();
+
+        let treasury_role = symbol_short!("TREASURY");
+        rbac.create_role(&admin, &treasury_role, &rbac.default_admin_role());
+
+        let withdrawer = Address::generate(&env);
+        let treasurer = Address::generate(&env);
+        rbac.grant_role(&admin, &WITHDRAWER_ROLE, &withdrawer, &0);
+        rbac.grant_role(&admin, &treasury_role, &treasurer, &0);
+
+        rbac.set_function_role(
+            &admin,
+            &vault.address,
+            &symbol_short!("withdraw"),
+            &treasury_role,
+        );
+
+        let result = vault.try_withdraw(&withdrawer, &400);
+        assert_eq!(result, Err(Ok(VaultError::NotAuthorized)));
+
+        vault.withdraw(&treasurer, &400);
+        assert_eq!(vault.get_balance(), 600);
+        assert_eq!(token_balance(&env, &vault, &treasurer), 400);
+    }
+
+    fn limit(per_tx: i128, per_window: i128) -> WithdrawalLimit {
+        WithdrawalLimit { per_tx, per_window }
+    }
+
+    #[test]
+    fn test_set_withdrawal_limit_requires_vault_admin() {
+        let (env, admin, _rbac, vault) = setup_with_rbac();
+        assert_eq!(vault.get_withdrawal_limit(&WITHDRAWER_ROLE), limit(0, 0));
+
+        let outsider = Address::generate(&env);
+        let result = vault.try_set_withdrawal_limit(&outsider, &WITHDRAWER_ROLE, &limit(100, 0));
+        assert_eq!(result, Err(Ok(VaultError::NotAuthorized)));
+
+        let result = vault.try_set_withdrawal_limit(&admin, &WITHDRAWER_ROLE, &limit(-1, 0));
+        assert_eq!(result, Err(Ok(VaultError::InvalidAmount)));
+
+        vault.set_withdrawal_limit(&admin, &WITHDRAWER_ROLE, &limit(100, 500));
+        assert_eq!(
+            vault.get_withdrawal_limit(&WITHDRAWER_ROLE),
+            limit(100, 500)
+        );
+    }
+
+    #[test]
+    fn test_per_transaction_limit() {
+        let (env, admin, rbac, vault) = setup_with_rbac();
+        vault.set_withdrawal_limit(&admin, &WITHDRAWER_ROLE, &limit(100, 0));
+
+        let withdrawer = Address::generate(&env);
+        rbac.grant_role(&admin, &WITHDRAWER_ROLE, &withdrawer, &0);
+
+        let result = vault.try_withdraw(&withdrawer, &101);
+        assert_eq!(result, Err(Ok(VaultError::LimitExceeded)));
+
+        // Without a window cap, repeated withdrawals at the limit are fine
+        vault.withdraw(&withdrawer, &100);
+        vault.withdraw(&withdrawer, &100);
+        assert_eq!(vault.get_balance(), 800);
+    }
+
+    #[test]
+    fn test_rolling_window_limit() {
+        let (env, admin, rbac, vault) = setup_with_rbac();
+        vault.set_withdrawal_limit(&admin, &WITHDRAWER_ROLE, &limit(0, 500));
+
+        let alice = Address::generate(&env);
+        let bob = Address::generate(&env);
+        rbac.grant_role(&admin, &WITHDRAWER_ROLE, &alice, &0);
+        rbac.grant_role(&admin, &WITHDRAWER_ROLE, &bob, &0);
+
+        let start = 10_000u64;
+        env.ledger().with_mut(|li| li.timestamp = start);
+        vault.withdraw(&alice, &300);
+
+        env.ledger()
+            .with_mut(|li| li.timestamp = start + 10 * 60 * 60);
+        vault.withdraw(&alice, &200);
+        assert_eq!(vault.get_spent(&alice), 500);
+
+        let result = vault.try_withdraw(&alice, &1);
+        assert_eq!(result, Err(Ok(VaultError::LimitExceeded)));
+
+        // The cap is per account
+        vault.withdraw(&bob, &100);
+
+        // One second before the first spend leaves the window
+        env.ledger()
+            .with_mut(|li| li.timestamp = start + SPEND_WINDOW - 1);
+        let result = vault.try_withdraw(&alice, &1);
+        assert_eq!(result, Err(Ok(VaultError::LimitExceeded)));
+
+        // The first spend has left the window; the second still counts
+        env.ledger()
+            .with_mut(|li| li.timestamp = start + SPEND_WINDOW);
+        assert_eq!(vault.get_spent(&alice), 200);
+        let result = vault.try_withdraw(&alice, &301);
+        assert_eq!(result, Err(Ok(VaultError::LimitExceeded)));
+        vault.withdraw(&alice, &300);
+
+        // Both earlier spends have left the window
+        env.ledger()
+            .with_mut(|li| li.timestamp = start + SPEND_WINDOW + 10 * 60 * 60);
+        assert_eq!(vault.get_spent(&alice), 300);
+    }
+
+    #[test]
+    fn test_window_follows_timestamps_not_ledgers() {
+        let (env, admin, rbac, vault) = setup_with_rbac();
+        vault.set_withdrawal_limit(&admin, &WITHDRAWER_ROLE, &limit(0, 500));
+
+        let alice = Address::generate(&env);
+        rbac.grant_role(&admin, &WITHDRAWER_ROLE, &alice, &0);
+        env.ledger().with_mut(|li| li.timestamp = 10_000);
+        vault.withdraw(&alice, &500);
+
+        // Ledgers close faster than 5s: more than a day's worth of them in an hour
+        env.ledger().with_mut(|li| {
+            li.sequence_number += (SPEND_WINDOW / 5) as u32 + 1;
+            li.timestamp += 60 * 60;
+        });
+        assert_eq!(vault.get_spent(&alice), 500);
+        let result = vault.try_withdraw(&alice, &1);
+        assert_eq!(result, Err(Ok(VaultError::LimitExceeded)));
+    }
+
+    #[test]
+    fn test_spend_records_are_capped() {
+        let (env, admin, rbac, vault) = setup_with_rbac();
+        vault.set_withdrawal_limit(&admin, &WITHDRAWER_ROLE, &limit(0, 100));
+
+        let alice = Address::generate(&env);
+        rbac.grant_role(&admin, &WITHDRAWER_ROLE, &alice, &0);
+        let start = 10_000u64;
+        for i in 0..(MAX_SPENDS as u64 + 4) {
+            env.ledger().with_mut(|li| li.timestamp = start + i);
+            vault.withdraw(&alice, &1);
+        }
+
+        let records = env.as_contract(&vault.address, || {
+            env.storage()
+                .temporary()
+                .get::<_, Vec<Spend>>(&DataKey::Spent(alice.clone()))
+                .unwrap()
+        });
+        assert_eq!(records.len(), MAX_SPENDS);
+        assert_eq!(vault.get_spent(&alice), MAX_SPENDS as i128 + 4);
+
+        // Merged amounts leave the window with the later of their timestamps
+        env.ledger()
+            .with_mut(|li| li.timestamp = start + SPEND_WINDOW + 4);
+        assert_eq!(vault.get_spent(&alice), MAX_SPENDS as i128 - 1);
+    }
+
+    #[test]
+    fn test_limits_follow_mapped_role() {
+        let (env, admin, rbac, vault) = setup_with_rbac();
+        vault.set_withdrawal_limit(&admin, &WITHDRAWER_ROLE, &limit(100, 0));
+
+        let treasury_role = symbol_short!("TREASURY");
+        rbac.create_role(&admin, &treasury_role, &rbac.default_admin_role());
+        let treasurer = Address::generate(&env);
+        rbac.grant_role(&admin, &treasury_role, &treasurer, &0);
+        rbac.set_function_role(
+            &admin,
+            &vault.address,
+            &symbol_short!("withdraw"),
+            &treasury_role,
+        );
+
+        // TREASURY has no limit configured
+        vault.withdraw(&treasurer, &400);
+
+        vault.set_withdrawal_limit(&admin, &treasury_role, &limit(300, 0));
+        let result = vault.try_withdraw(&treasurer, &400);
+        assert_eq!(result, Err(Ok(VaultError::LimitExceeded)));
+    }
+
+    /// Register and initialize a second RBAC contract with the vault's roles.
+    fn setup_new_rbac(env: &Env, admin: &Address, vault: &Address) -> RbacContractClient<'static> {
+        let rbac = RbacContractClient::new(env, &env.register(RbacContract, ()));
+        rbac.initialize(admin);
+        rbac.create_role(admin, &WITHDRAWER_ROLE, &rbac.default_admin_role());
+        rbac.set_function_role(admin, vault, &symbol_short!("withdraw"), &WITHDRAWER_ROLE);
+        rbac
+    }
+
+    #[test]
+    fn test_set_rbac_address() {
+        let (env, admin, rbac, vault) = setup_with_rbac();
+        let new_rbac = setup_new_rbac(&env, &admin, &vault.address);
+
+        let withdrawer = Address::generate(&env);
+        rbac.grant_role(&admin, &WITHDRAWER_ROLE, &withdrawer, &0);
+
+        vault.set_rbac_address(&admin, &new_rbac.address);
+        assert_eq!(
+            env.events().all().filter_by_contract(&vault.address),
+            vec![
+                &env,
+                (
+                    vault.address.clone(),
+                    (symbol_short!("RbacRebnd"), rbac.address.clone()).into_val(&env),
+                    (new_rbac.address.clone(), admin.clone()).into_val(&env),
+                ),
+            ]
+        );
+        assert_eq!(vault.get_rbac_address(), Some(new_rbac.address.clone()));
+
+        // Grants in the old contract no longer count
+        let result = vault.try_withdraw(&withdrawer, &100);
+        assert_eq!(result, Err(Ok(VaultError::NotAuthorized)));
+        new_rbac.grant_role(&admin, &WITHDRAWER_ROLE, &withdrawer, &0);
+        vault.withdraw(&withdrawer, &100);
+
+        // VLT_ADMIN is now checked in the new contract, where nobody holds it
+        let result = vault.try_set_rbac_address(&admin, &rbac.address);
+        assert_eq!(result, Err(Ok(VaultError::NotAuthorized)));
+    }
+
+    #[test]
+    fn test_set_rbac_address_rejects_invalid() {
+        let (env, admin, rbac, vault) = setup_with_rbac();
+        let new_rbac = setup_new_rbac(&env, &admin, &vault.address);
+
+        let outsider = Address::generate(&env);
+        let result = vault.try_set_rbac_address(&outsider, &new_rbac.address);
+        assert_eq!(result, Err(Ok(VaultError::NotAuthorized)));
+
+        // Neither a plain address nor a non-RBAC contract is accepted
+        let token = vault.get_token().unwrap();
+        for invalid in [Address::generate(&env), token] {
+            let result = vault.try_set_rbac_address(&admin, &invalid);
+            assert_eq!(result, Err(Ok(VaultError::InvalidRbacContract)));
+        }
+        assert_eq!(vault.get_rbac_address(), Some(rbac.address));
+    }
+
+    #[test]
+    fn test_large_withdrawal_needs_approval() {
+        let (env, admin, rbac, vault) = setup_with_rbac();
+        vault.set_approval_threshold(&admin, &300);
+        assert_eq!(vault.get_approval_threshold(), 300);
+
+        let requester = Address::generate(&env);
+        let approver = Address::generate(&env);
+        rbac.grant_role(&admin, &WITHDRAWER_ROLE, &requester, &0);
+        rbac.grant_role(&admin, &APPROVER_ROLE, &approver, &0);
+
+        // At the threshold withdraw works directly; above it needs a request
+        vault.withdraw(&requester, &300);
+        let result = vault.try_withdraw(&requester, &301);
+        assert_eq!(result, Err(Ok(VaultError::ApprovalRequired)));
+
+        let id = vault.request_withdrawal(&requester, &500);
+        assert_eq!(
+            vault.get_request(&id),
+            Some(WithdrawalRequest {
+                requester: requester.clone(),
+                amount: 500,
+                approver: None
+            })
+        );
+        let result = vault.try_execute_withdrawal(&requester, &id);
+        assert_eq!(result, Err(Ok(VaultError::NotApproved)));
+
+        vault.approve_withdrawal(&approver, &id);
+        let result = vault.try_approve_withdrawal(&approver, &id);
+        assert_eq!(result, Err(Ok(VaultError::AlreadyApproved)));
+
+        // Only the requester executes, and funds go to the requester
+        let result = vault.try_execute_withdrawal(&approver, &id);
+        assert_eq!(result, Err(Ok(VaultError::NotAuthorized)));
+        vault.execute_withdrawal(&requester, &id);
+        assert_eq!(vault.get_balance(), 200);
+        assert_eq!(token_balance(&env, &vault, &requester), 800);
+
+        assert_eq!(vault.get_request(&id), None);
+        let result = vault.try_execute_withdrawal(&requester, &id);
+        assert_eq!(result, Err(Ok(VaultError::RequestNotFound)));
+    }
+
+    #[test]
+    fn test_approval_needs_separate_approver() {
+        let (env, admin, rbac, vault) = setup_with_rbac();
+        vault.set_approval_threshold(&admin, &300);
+
+        let requester = Address::generate(&env);
+        let outsider = Address::generate(&env);
+        rbac.grant_role(&admin, &WITHDRAWER_ROLE, &requester, &0);
+        rbac.grant_role(&admin, &APPROVER_ROLE, &requester, &0);
+
+        let outsider_request = vault.try_request_withdrawal(&outsider, &500);
+        assert_eq!(outsider_request, Err(Ok(VaultError::NotAuthorized)));
+
+        let id = vault.request_withdrawal(&requester, &500);
+        let result = vault.try_approve_withdrawal(&outsider, &id);
+        assert_eq!(result, Err(Ok(VaultError::NotAuthorized)));
+
+        // Holding both roles does not allow approving one's own request
+        let result = vault.try_approve_withdrawal(&requester, &id);
+        assert_eq!(result, Err(Ok(VaultError::SelfApproval)));
+        assert_eq!(vault.get_request(&id).unwrap().approver, None);
+    }
+
+    #[test]
+    fn test_request_at_or_below_threshold_fails() {
+        let (env, admin, rbac, vault) = setup_with_rbac();
+        let requester = Address::generate(&env);
+        rbac.grant_role(&admin, &WITHDRAWER_ROLE, &requester, &0);
+
+        // Without a threshold nothing needs approval
+        let result = vault.try_request_withdrawal(&requester, &500);
+        assert_eq!(result, Err(Ok(VaultError::ApprovalNotRequired)));
+
+        vault.set_approval_threshold(&admin, &300);
+        let result = vault.try_request_withdrawal(&requester, &300);
+        assert_eq!(result, Err(Ok(VaultError::ApprovalNotRequired)));
+        vault.request_withdrawal(&requester, &301);
+    }
+
+    #[test]
+    fn test_pending_requests_extend_ttl() {
+        let (env, admin, rbac, vault) = setup_with_rbac();
+        vault.set_approval_threshold(&admin, &100);
+
+        let requester = Address::generate(&env);
+        let approver = Address::generate(&env);
+        rbac.grant_role(&admin, &WITHDRAWER_ROLE, &requester, &0);
+        rbac.grant_role(&admin, &APPROVER_ROLE, &approver, &0);
+        let request_ttl = |id: u64| {
+            env.as_contract(&vault.address, || {
+                env.storage().persistent().get_ttl(&DataKey::Request(id))
+            })
+        };
+
+        let id = vault.request_withdrawal(&requester, &500);
+        assert_eq!(request_ttl(id), REQUEST_BUMP_LEDGERS);
+
+        // Let the TTL run down, then approving extends it again
+        env.ledger().with_mut(|li| li.sequence_number += 1_000);
+        assert_eq!(request_ttl(id), REQUEST_BUMP_LEDGERS - 1_000);
+        vault.approve_withdrawal(&approver, &id);
+        assert_eq!(request_ttl(id), REQUEST_BUMP_LEDGERS);
+    }
+
+    #[test]
+    fn test_cancel_withdrawal() {
+        let (env, admin, rbac, vault) = setup_with_rbac();
+        vault.set_approval_threshold(&admin, &100);
+
+        let requester = Address::generate(&env);
+        let outsider = Address::generate(&env);
+        rbac.grant_role(&admin, &WITHDRAWER_ROLE, &requester, &0);
+
+        let first = vault.request_withdrawal(&requester, &500);
+        let second = vault.request_withdrawal(&requester, &500);
+        assert_ne!(first, second);
+
+        let result = vault.try_cancel_withdrawal(&outsider, &first);
+        assert_eq!(result, Err(Ok(VaultError::NotAuthorized)));
+
+        vault.cancel_withdrawal(&requester, &first);
+        assert_eq!(vault.get_request(&first), None);
+
+        // VLT_ADMIN holders can cancel any request
+        vault.cancel_withdrawal(&admin, &second);
+        assert_eq!(vault.get_request(&second), None);
+
+        let result = vault.try_cancel_withdrawal(&requester, &second);
+        assert_eq!(result, Err(Ok(VaultError::RequestNotFound)));
+    }
+
+    #[test]
+    fn test_revoked_requester_cannot_execute() {
+        let (env, admin, rbac, vault) = setup_with_rbac();
+        vault.set_approval_threshold(&admin, &100);
+
+        let requester = Address::generate(&env);
+        let approver = Address::generate(&env);
+        rbac.grant_role(&admin, &WITHDRAWER_ROLE, &requester, &0);
+        rbac.grant_role(&admin, &APPROVER_ROLE, &approver, &0);
+
+        let id = vault.request_withdrawal(&requester, &500);
+        vault.approve_withdrawal(&approver, &id);
+        rbac.revoke_role(&admin, &WITHDRAWER_ROLE, &requester);
+
+        let result = vault.try_execute_withdrawal(&requester, &id);
+        assert_eq!(result, Err(Ok(VaultError::NotAuthorized)));
+        assert_eq!(vault.get_balance(), 1000);
+    }
+
+    #[test]
+    fn test_revoked_approver_voids_approval() {
+        let (env, admin, rbac, vault) = setup_with_rbac();
+        vault.set_approval_threshold(&admin, &100);
+
+        let requester = Address::generate(&env);
+        let approver = Address::generate(&env);
+        rbac.grant_role(&admin, &WITHDRAWER_ROLE, &requester, &0);
+        rbac.grant_role(&admin, &APPROVER_ROLE, &approver, &0);
+
+        let id = vault.request_withdrawal(&requester, &500);
+        vault.approve_withdrawal(&approver, &id);
+        rbac.revoke_role(&admin, &APPROVER_ROLE, &approver);
+
+        let result = vault.try_execute_withdrawal(&requester, &id);
+        assert_eq!(result, Err(Ok(VaultError::NotApproved)));
+        assert_eq!(vault.get_balance(), 1000);
+    }
+
+    #[test]
+    fn test_approval_replaces_per_transaction_limit() {
+        let (env, admin, rbac, vault) = setup_with_rbac();
+        vault.set_withdrawal_limit(&admin, &WITHDRAWER_ROLE, &limit(100, 600));
+        vault.set_approval_threshold(&admin, &100);
+
+        let requester = Address::generate(&env);
+        let approver = Address::generate(&env);
+        rbac.grant_role(&admin, &WITHDRAWER_ROLE, &requester, &0);
+        rbac.grant_role(&admin, &APPROVER_ROLE, &approver, &0);
+
+        let id = vault.request_withdrawal(&requester, &500);
+        vault.approve_withdrawal(&approver, &id);
+        vault.execute_withdrawal(&requester, &id);
+
+        // The approved amount counts towards the rolling window
+        assert_eq!(vault.get_spent(&requester), 500);
+        let id = vault.request_withdrawal(&requester, &200);
+        vault.approve_withdrawal(&approver, &id);
+        let result = vault.try_execute_withdrawal(&requester, &id);
+        assert_eq!(result, Err(Ok(VaultError::LimitExceeded)));
+    }
 }
@@ -0,0 +1,250 @@
+#![cfg(test)]
+
+//! End-to-end tests: SecureVault, RbacContract and a Stellar Asset Contract in one `Env`.
+
+use soroban_sdk::testutils::{Address as _, Ledger};
+use soroban_sdk::token::{StellarAssetClient, TokenClient};
+use soroban_sdk::{contract, contractimpl, symbol_short, Address, Env, Symbol};
+use stellar_keystone_rbac::{RbacContract, RbacContractClient};
+use stellar_keystone_secure_vault::{
+    SecureVaultContract, SecureVaultContractClient, VaultError, WithdrawalLimit,
+};
+
+const WITHDRAWER_ROLE: Symbol = symbol_short!("WITHDRAW");
+const APPROVER_ROLE: Symbol = symbol_short!("APPROVER");
+const VAULT_ADMIN_ROLE: Symbol = symbol_short!("VLT_ADMIN");
+
+/// A deployed contract that is not an RBAC contract.
+#[contract]
+struct NotRbac;
+
+#[contractimpl]
+impl NotRbac {
+    pub fn has_role(_env: Env, _role: Symbol, _account: Address) -> bool {
+        true
+    }
+}
+
+struct Setup {
+    env: Env,
+    admin: Address,
+    rbac: RbacContractClient<'static>,
+    vault: SecureVaultContractClient<'static>,
+    token: TokenClient<'static>,
+}
+
+/// Deploy all three contracts: the vault holds 1000 tokens and `withdraw` is mapped
+/// to WITHDRAW.
+fn setup() -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let rbac = RbacContractClient::new(&env, &env.register(RbacContract, ()));
+    let admin = Address::generate(&env);
+    rbac.initialize(&admin);
+
+    let token_id = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    let vault = SecureVaultContractClient::new(
+        &env,
+        &env.register(SecureVaultContract, (Address::generate(&env),)),
+    );
+    vault.initialize(&rbac.address, &token_id);
+    StellarAssetClient::new(&env, &token_id).mint(&vault.address, &1000);
+
+    let token = TokenClient::new(&env, &token_id);
+    Setup {
+        env,
+        admin,
+        rbac,
+        vault,
+        token,
+    }
+}
+
+/// Create the vault's roles and map `withdraw`, as an operator would after deploying.
+fn configure_roles(s: &Setup) {
+    let admin_role = s.rbac.default_admin_role();
+    for role in [WITHDRAWER_ROLE, APPROVER_ROLE, VAULT_ADMIN_ROLE] {
+        s.rbac.create_role(&s.admin, &role, &admin_role);
+    }
+    s.rbac.grant_role(&s.admin, &VAULT_ADMIN_ROLE, &s.admin, &0);
+    s.rbac.set_function_role(
+        &s.admin,
+        &s.vault.address,
+        &symbol_short!("withdraw"),
+        &WITHDRAWER_ROLE,
+    );
+}
+
+#[test]
+fn test_create_grant_withdraw_revoke_flow() {
+    let s = setup();
+    let alice = Address::generate(&s.env);
+
+    // Before the roles exist nobody can withdraw
+    let result = s.vault.try_withdraw(&alice, &100);
+    assert_eq!(result, Err(Ok(VaultError::NotAuthorized)));
+
+    configure_roles(&s);
+    let result = s.vault.try_withdraw(&alice, &100);
+    assert_eq!(result, Err(Ok(VaultError::NotAuthorized)));
+
+    s.rbac.grant_role(&s.admin, &WITHDRAWER_ROLE, &alice, &0);
+    s.vault.withdraw(&alice, &100);
+    s.vault.withdraw(&alice, &150);
+    assert_eq!(s.token.balance(&alice), 250);
+    assert_eq!(s.vault.get_balance(), 750);
+
+    s.rbac.revoke_role(&s.admin, &WITHDRAWER_ROLE, &alice);
+    let result = s.vault.try_withdraw(&alice, &100);
+    assert_eq!(result, Err(Ok(VaultError::NotAuthorized)));
+    assert_eq!(s.token.balance(&alice), 250);
+    assert_eq!(s.vault.get_balance(), 750);
+
+    // Funds deposited later are still guarded
+    let depositor = Address::generate(&s.env);
+    StellarAssetClient::new(&s.env, &s.token.address).mint(&depositor, &500);
+    s.vault.deposit(&depositor, &500);
+    assert_eq!(s.vault.get_balance(), 1250);
+    let result = s.vault.try_withdraw(&alice, &100);
+    assert_eq!(result, Err(Ok(VaultError::NotAuthorized)));
+}
+
+#[test]
+fn test_role_expiry_mid_flow() {
+    let s = setup();
+    configure_roles(&s);
+
+    let start = 1_000u64;
+    s.env.ledger().with_mut(|li| li.timestamp = start);
+
+    let alice = Address::generate(&s.env);
+    s.rbac
+        .grant_role(&s.admin, &WITHDRAWER_ROLE, &alice, &(start + 3600));
+    s.vault.withdraw(&alice, &100);
+
+    // Last second of the grant
+    s.env.ledger().with_mut(|li| li.timestamp = start + 3599);
+    s.vault.withdraw(&alice, &100);
+
+    // Expired without a revoke
+    s.env.ledger().with_mut(|li| li.timestamp = start + 3600);
+    let result = s.vault.try_withdraw(&alice, &100);
+    assert_eq!(result, Err(Ok(VaultError::NotAuthorized)));
+    assert_eq!(s.token.balance(&alice), 200);
+
+    // Extending the grant restores access
+    s.rbac
+        .grant_role(&s.admin, &WITHDRAWER_ROLE, &alice, &(start + 7200));
+    s.vault.withdraw(&alice, &100);
+    assert_eq!(s.token.balance(&alice), 300);
+}
+
+#[test]
+fn test_role_expiry_between_approval_and_execution() {
+    let s = setup();
+    configure_roles(&s);
+    s.vault.set_approval_threshold(&s.admin, &100);
+
+    let start = 1_000u64;
+    s.env.ledger().with_mut(|li| li.timestamp = start);
+
+    let alice = Address::generate(&s.env);
+    let bob = Address::generate(&s.env);
+    s.rbac
+        .grant_role(&s.admin, &WITHDRAWER_ROLE, &alice, &(start + 3600));
+    s.rbac.grant_role(&s.admin, &APPROVER_ROLE, &bob, &0);
+
+    let id = s.vault.request_withdrawal(&alice, &500);
+    s.vault.approve_withdrawal(&bob, &id);
+
+    s.env.ledger().with_mut(|li| li.timestamp = start + 3600);
+    let result = s.vault.try_execute_withdrawal(&alice, &id);
+    assert_eq!(result, Err(Ok(VaultError::NotAuthorized)));
+    assert_eq!(s.vault.get_balance(), 1000);
+
+    // The approved request survives a re-grant
+    s.rbac.grant_role(&s.admin, &WITHDRAWER_ROLE, &alice, &0);
+    s.vault.execute_withdrawal(&alice, &id);
+    assert_eq!(s.token.balance(&alice), 500);
+}
+
+#[test]
+fn test_approver_expiry_blocks_approval() {
+    let s = setup();
+    configure_roles(&s);
+    s.vault.set_approval_threshold(&s.admin, &100);
+
+    let start = 1_000u64;
+    s.env.ledger().with_mut(|li| li.timestamp = start);
+
+    let alice = Address::generate(&s.env);
+    let bob = Address::generate(&s.env);
+    s.rbac.grant_role(&s.admin, &WITHDRAWER_ROLE, &alice, &0);
+    s.rbac
+        .grant_role(&s.admin, &APPROVER_ROLE, &bob, &(start + 60));
+
+    let id = s.vault.request_withdrawal(&alice, &500);
+    s.env.ledger().with_mut(|li| li.timestamp = start + 60);
+    let result = s.vault.try_approve_withdrawal(&bob, &id);
+    assert_eq!(result, Err(Ok(VaultError::NotAuthorized)));
+}
+
+#[test]
+fn test_limits_configured_through_rbac() {
+    let s = setup();
+    configure_roles(&s);
+
+    let alice = Address::generate(&s.env);
+    s.rbac.grant_role(&s.admin, &WITHDRAWER_ROLE, &alice, &0);
+
+    // VLT_ADMIN is checked in RBAC: revoking it takes configuration rights away
+    let limit = WithdrawalLimit {
+        per_tx: 50,
+        per_window: 0,
+    };
+    s.vault
+        .set_withdrawal_limit(&s.admin, &WITHDRAWER_ROLE, &limit);
+    s.rbac.revoke_role(&s.admin, &VAULT_ADMIN_ROLE, &s.admin);
+    let result =
+        s.vault
+            .try_set_withdrawal_limit(&s.admin, &WITHDRAWER_ROLE, &WithdrawalLimit::default());
+    assert_eq!(result, Err(Ok(VaultError::NotAuthorized)));
+
+    let result = s.vault.try_withdraw(&alice, &51);
+    assert_eq!(result, Err(Ok(VaultError::LimitExceeded)));
+    s.vault.withdraw(&alice, &50);
+}
+
+#[test]
+fn test_rbac_address_is_not_an_rbac_contract() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let token_id = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    let alice = Address::generate(&env);
+
+    // A contract without the RBAC interface (even one answering `has_role`), the
+    // token contract itself, and an address with no contract at all
+    let not_rbac = env.register(NotRbac, ());
+    for rbac_address in [not_rbac, token_id.clone(), Address::generate(&env)] {
+        let vault = SecureVaultContractClient::new(
+            &env,
+            &env.register(SecureVaultContract, (Address::generate(&env),)),
+        );
+        vault.initialize(&rbac_address, &token_id);
+        StellarAssetClient::new(&env, &token_id).mint(&vault.address, &1000);
+
+        let result = vault.try_withdraw(&alice, &100);
+        assert_eq!(result, Err(Ok(VaultError::NotAuthorized)));
+        let result = vault.try_request_withdrawal(&alice, &100);
+        assert_eq!(result, Err(Ok(VaultError::NotAuthorized)));
+        let result = vault.try_set_approval_threshold(&alice, &100);
+        assert_eq!(result, Err(Ok(VaultError::NotAuthorized)));
+        assert_eq!(vault.get_balance(), 1000);
+    }
+}
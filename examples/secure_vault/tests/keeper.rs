@@ -0,0 +1,170 @@
+#![cfg(test)]
+
+//! A keeper contract holding WITHDRAWER calls through to the vault.
+//!
+//! Auth is enforced for real here (no `mock_all_auths` during keeper calls), so
+//! the vault only accepts a contract caller that is its direct invoker.
+
+use soroban_sdk::testutils::Address as _;
+use soroban_sdk::token::{StellarAssetClient, TokenClient};
+use soroban_sdk::{contract, contractimpl, symbol_short, Address, Env, Symbol};
+use stellar_keystone_rbac::{RbacContract, RbacContractClient, RbacError};
+use stellar_keystone_rbac_client::authorize_check_call;
+use stellar_keystone_secure_vault::{SecureVaultContract, SecureVaultContractClient};
+
+const WITHDRAWER_ROLE: Symbol = symbol_short!("WITHDRAW");
+
+/// Withdraws on a schedule, e.g. to top up a hot wallet.
+#[contract]
+struct Keeper;
+
+#[contractimpl]
+impl Keeper {
+    /// Withdraw from `vault` as this contract.
+    pub fn run(env: Env, vault: Address, rbac: Address, amount: i128) {
+        authorize_check_call(&env, &rbac, &vault, &symbol_short!("withdraw"));
+        let this = env.current_contract_address();
+        SecureVaultContractClient::new(&env, &vault).withdraw(&this, &amount);
+    }
+
+    /// Withdraw from `vault` as this contract, without authorizing the RBAC check.
+    pub fn run_unauthorized(env: Env, vault: Address, amount: i128) {
+        let this = env.current_contract_address();
+        SecureVaultContractClient::new(&env, &vault).withdraw(&this, &amount);
+    }
+
+    /// Withdraw from `vault` naming another address as the caller.
+    pub fn run_as(env: Env, vault: Address, caller: Address, amount: i128) {
+        SecureVaultContractClient::new(&env, &vault).withdraw(&caller, &amount);
+    }
+}
+
+struct Setup {
+    env: Env,
+    admin: Address,
+    rbac: RbacContractClient<'static>,
+    vault: SecureVaultContractClient<'static>,
+    keeper: KeeperClient<'static>,
+}
+
+fn setup() -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let rbac_id = env.register(RbacContract, ());
+    let rbac = RbacContractClient::new(&env, &rbac_id);
+    let admin = Address::generate(&env);
+    rbac.initialize(&admin);
+    rbac.create_role(&admin, &WITHDRAWER_ROLE, &rbac.default_admin_role());
+
+    let vault_id = env.register(SecureVaultContract, (Address::generate(&env),));
+    let vault = SecureVaultContractClient::new(&env, &vault_id);
+    let token = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    vault.initialize(&rbac_id, &token);
+    StellarAssetClient::new(&env, &token).mint(&vault_id, &1000);
+    rbac.set_function_role(
+        &admin,
+        &vault_id,
+        &symbol_short!("withdraw"),
+        &WITHDRAWER_ROLE,
+    );
+
+    let keeper = KeeperClient::new(&env, &env.register(Keeper, ()));
+
+    Setup {
+        env,
+        admin,
+        rbac,
+        vault,
+        keeper,
+    }
+}
+
+#[test]
+fn test_keeper_with_role_withdraws() {
+    let s = setup();
+    s.rbac
+        .grant_role_to_contract(&s.admin, &WITHDRAWER_ROLE, &s.keeper.address, &0);
+
+    // Nobody signs: the keeper authorizes by being the invoker
+    s.env.set_auths(&[]);
+    s.keeper.run(&s.vault.address, &s.rbac.address, &400);
+    assert_eq!(s.vault.get_balance(), 600);
+
+    let token = TokenClient::new(&s.env, &s.vault.get_token().unwrap());
+    assert_eq!(token.balance(&s.keeper.address), 400);
+}
+
+#[test]
+fn test_keeper_without_role_rejected() {
+    let s = setup();
+
+    s.env.set_auths(&[]);
+    assert!(s
+        .keeper
+        .try_run(&s.vault.address, &s.rbac.address, &400)
+        .is_err());
+    assert_eq!(s.vault.get_balance(), 1000);
+}
+
+#[test]
+fn test_keeper_must_authorize_rbac_check() {
+    let s = setup();
+    s.rbac
+        .grant_role_to_contract(&s.admin, &WITHDRAWER_ROLE, &s.keeper.address, &0);
+
+    // The RBAC check runs below the vault, so it is not covered by the keeper's
+    // implicit invoker auth
+    s.env.set_auths(&[]);
+    assert!(s
+        .keeper
+        .try_run_unauthorized(&s.vault.address, &400)
+        .is_err());
+    assert_eq!(s.vault.get_balance(), 1000);
+}
+
+#[test]
+fn test_contract_cannot_act_as_another_role_holder() {
+    let s = setup();
+    s.rbac
+        .grant_role_to_contract(&s.admin, &WITHDRAWER_ROLE, &s.keeper.address, &0);
+    let imposter = KeeperClient::new(&s.env, &s.env.register(Keeper, ()));
+
+    // The imposter is the invoker, not the keeper it names as caller
+    s.env.set_auths(&[]);
+    assert!(imposter
+        .try_run_as(&s.vault.address, &s.keeper.address, &400)
+        .is_err());
+    assert_eq!(s.vault.get_balance(), 1000);
+}
+
+#[test]
+fn test_revoked_keeper_rejected() {
+    let s = setup();
+    s.rbac
+        .grant_role_to_contract(&s.admin, &WITHDRAWER_ROLE, &s.keeper.address, &0);
+    s.rbac
+        .revoke_role(&s.admin, &WITHDRAWER_ROLE, &s.keeper.address);
+
+    s.env.set_auths(&[]);
+    assert!(s
+        .keeper
+        .try_run(&s.vault.address, &s.rbac.address, &400)
+        .is_err());
+    assert_eq!(s.vault.get_balance(), 1000);
+}
+
+#[test]
+fn test_role_grant_requires_deployed_contract() {
+    let s = setup();
+
+    let result = s.rbac.try_grant_role_to_contract(
+        &s.admin,
+        &WITHDRAWER_ROLE,
+        &Address::generate(&s.env),
+        &0,
+    );
+    assert_eq!(result, Err(Ok(RbacError::NotAContract)));
+}
@@ -0,0 +1,437 @@
+//! # RBAC Account Contract
+//!
+//! A custom account (smart wallet) whose signers are governed by a deployed
+//! `RbacContract`. Access to the account is granted and withdrawn with
+//! `grant_role` / `revoke_role` instead of by rotating keys.
+//!
+//! ## Authorization Rules
+//! `__check_auth` accepts a transaction when:
+//! 1. At least one signer address is supplied
+//! 2. Every signer holds the account's signer role and authorizes the signature payload
+//! 3. For every invoked `(contract, function)` with a permission mapping, at least one
+//!    signer holds the mapped role. Unmapped calls only need rule 2.
+//! 4. No contract is deployed on the account's behalf. A deployment has no
+//!    `(contract, function)` to map to a role, so it would otherwise need only rule 2.
+//!
+//! Signers are addresses (accounts or other contracts), so each signer proves control
+//! through its own auth when the account calls `require_auth_for_args` on it.
+//!
+//! ## Usage Flow
+//! 1. Deploy RBAC contract and create a signer role (e.g. `TREASURY`)
+//! 2. Deploy RbacAccount with the RBAC contract address and the signer role
+//! 3. Grant the signer role to team members
+//! 4. Optionally map sensitive calls to stricter roles with `set_permission`
+
+#![no_std]
+
+use soroban_sdk::auth::{Context, CustomAccountInterface};
+use soroban_sdk::crypto::Hash;
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contracttype, Address, Env, IntoVal, Symbol, Vec,
+};
+use stellar_keystone_rbac_client::RbacClient;
+
+// =============================================================================
+// Storage Keys
+// =============================================================================
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    /// The RBAC contract address (instance)
+    RbacAddress,
+    /// Role every signer must hold (instance)
+    SignerRole,
+    /// Maps (contract, function) to the role required to call it (persistent)
+    Permission(Address, Symbol),
+}
+
+// =============================================================================
+// Errors
+// =============================================================================
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum AccountError {
+    /// No signers were supplied
+    NoSigners = 1,
+    /// A signer does not hold the signer role
+    SignerNotAuthorized = 2,
+    /// No signer holds the role mapped to an invoked function
+    CallNotPermitted = 3,
+    /// The account was asked to authorize a contract deployment
+    DeployNotPermitted = 4,
+}
+
+// =============================================================================
+// Contract
+// =============================================================================
+
+#[contract]
+pub struct RbacAccount;
+
+#[contractimpl]
+impl RbacAccount {
+    /// Create the account.
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    /// * `rbac_address` - The address of the deployed RBAC contract
+    /// * `signer_role` - The role every signer must hold
+    pub fn __constructor(env: Env, rbac_address: Address, signer_role: Symbol) {
+        env.storage()
+            .instance()
+            .set(&DataKey::RbacAddress, &rbac_address);
+        env.storage()
+            .instance()
+            .set(&DataKey::SignerRole, &signer_role);
+    }
+
+    /// Require a role for calls to a specific contract function.
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    /// * `contract` - The contract being called
+    /// * `fn_name` - The function being called
+    /// * `role` - The role at least one signer must hold
+    ///
+    /// # Authorization
+    /// The account itself must authorize, i.e. its signers pass `__check_auth`.
+    /// Map `(this account, set_permission)` to lock down permission changes.
+    pub fn set_permission(env: Env, contract: Address, fn_name: Symbol, role: Symbol) {
+        env.current_contract_address().require_auth();
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::Permission(contract, fn_name), &role);
+    }
+
+    /// Remove the role requirement for a contract function.
+    ///
+    /// # Authorization
+    /// The account itself must authorize.
+    pub fn remove_permission(env: Env, contract: Address, fn_name: Symbol) {
+        env.current_contract_address().require_auth();
+
+        env.storage()
+            .persistent()
+            .remove(&DataKey::Permission(contract, fn_name));
+    }
+
+    /// Get the role required for a contract function, if any.
+    pub fn get_permission(env: Env, contract: Address, fn_name: Symbol) -> Option<Symbol> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Permission(contract, fn_name))
+    }
+
+    /// Get the RBAC contract address.
+    pub fn get_rbac_address(env: Env) -> Address {
+        Self::rbac_address(&env)
+    }
+
+    /// Get the role every signer must hold.
+    pub fn get_signer_role(env: Env) -> Symbol {
+        Self::signer_role(&env)
+    }
+
+    // =========================================================================
+    // Internal Helpers
+    // =========================================================================
+
+    fn rbac_address(env: &Env) -> Address {
+        env.storage().instance().get(&DataKey::RbacAddress).unwrap()
+    }
+
+    fn signer_role(env: &Env) -> Symbol {
+        env.storage().instance().get(&DataKey::SignerRole).unwrap()
+    }
+}
+
+#[contractimpl]
+impl CustomAccountInterface for RbacAccount {
+    /// Signer addresses. Each must authorize the signature payload.
+    type Signature = Vec<Address>;
+    type Error = AccountError;
+
+    fn __check_auth(
+        env: Env,
+        signature_payload: Hash<32>,
+        signers: Vec<Address>,
+        auth_contexts: Vec<Context>,
+    ) -> Result<(), AccountError> {
+        if signers.is_empty() {
+            return Err(AccountError::NoSigners);
+        }
+
+        let rbac = RbacClient::new(&env, &Self::rbac_address(&env));
+        let signer_role = Self::signer_role(&env);
+
+        // Every signer must hold the signer role and sign this payload
+        for signer in signers.iter() {
+            if !rbac.has_role(&signer_role, &signer) {
+                return Err(AccountError::SignerNotAuthorized);
+            }
+            signer.require_auth_for_args((signature_payload.to_bytes(),).into_val(&env));
+        }
+
+        // Mapped calls additionally need a signer holding the mapped role, and
+        // deployments are refused
+        for context in auth_contexts.iter() {
+            let call = match context {
+                Context::Contract(call) => call,
+                Context::CreateContractHostFn(_) | Context::CreateContractWithCtorHostFn(_) => {
+                    return Err(AccountError::DeployNotPermitted);
+                }
+            };
+            let required: Option<Symbol> = env
+                .storage()
+                .persistent()
+                .get(&DataKey::Permission(call.contract, call.fn_name));
+            if let Some(role) = required {
+                if !signers.iter().any(|signer| rbac.has_role(&role, &signer)) {
+                    return Err(AccountError::CallNotPermitted);
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+// =============================================================================
+// Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use soroban_sdk::auth::{ContractContext, ContractExecutable, CreateContractHostFnContext};
+    use soroban_sdk::testutils::{Address as _, MockAuth, MockAuthInvoke};
+    use soroban_sdk::{symbol_short, vec, BytesN, IntoVal};
+    use stellar_keystone_rbac::{RbacContract, RbacContractClient};
+
+    const TREASURY_ROLE: Symbol = symbol_short!("TREASURY");
+    const PAYOUT_ROLE: Symbol = symbol_short!("PAYOUT");
+
+    fn setup() -> (
+        Env,
+        Address,
+        RbacContractClient<'static>,
+        RbacAccountClient<'static>,
+    ) {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let rbac_id = env.register(RbacContract, ());
+        let rbac = RbacContractClient::new(&env, &rbac_id);
+        let admin = Address::generate(&env);
+        rbac.initialize(&admin);
+        rbac.create_role(&admin, &TREASURY_ROLE, &rbac.default_admin_role());
+        rbac.create_role(&admin, &PAYOUT_ROLE, &rbac.default_admin_role());
+
+        let account_id = env.register(RbacAccount, (rbac_id, TREASURY_ROLE));
+        let account = RbacAccountClient::new(&env, &account_id);
+
+        (env, admin, rbac, account)
+    }
+
+    fn call_context(env: &Env, contract: &Address, fn_name: &str) -> Context {
+        Context::Contract(ContractContext {
+            contract: contract.clone(),
+            fn_name: Symbol::new(env, fn_name),
+            args: vec![env, 100i128.into_val(env)],
+        })
+    }
+
+    fn payload(env: &Env) -> BytesN<32> {
+        BytesN::from_array(env, &[1; 32])
+    }
+
+    fn check_auth(
+        env: &Env,
+        account: &Address,
+        signers: &Vec<Address>,
+        contexts: &Vec<Context>,
+    ) -> Result<(), Result<AccountError, soroban_sdk::InvokeError>> {
+        env.try_invoke_contract_check_auth::<AccountError>(
+            account,
+            &payload(env),
+            signers.into_val(env),
+            contexts,
+        )
+    }
+
+    #[test]
+    fn test_signer_with_role_is_accepted() {
+        let (env, admin, rbac, account) = setup();
+
+        let member = Address::generate(&env);
+        rbac.grant_role(&admin, &TREASURY_ROLE, &member, &0);
+
+        let target = Address::generate(&env);
+        let contexts = vec![&env, call_context(&env, &target, "transfer")];
+        assert_eq!(
+            check_auth(&env, &account.address, &vec![&env, member], &contexts),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn test_signer_without_role_is_rejected() {
+        let (env, _admin, _rbac, account) = setup();
+
+        let outsider = Address::generate(&env);
+        let target = Address::generate(&env);
+        let contexts = vec![&env, call_context(&env, &target, "transfer")];
+        assert_eq!(
+            check_auth(&env, &account.address, &vec![&env, outsider], &contexts),
+            Err(Ok(AccountError::SignerNotAuthorized))
+        );
+
+        assert_eq!(
+            check_auth(&env, &account.address, &Vec::new(&env), &contexts),
+            Err(Ok(AccountError::NoSigners))
+        );
+    }
+
+    #[test]
+    fn test_signer_must_sign_the_payload() {
+        let (env, admin, rbac, account) = setup();
+
+        let alice = Address::generate(&env);
+        let bob = Address::generate(&env);
+        rbac.grant_role(&admin, &TREASURY_ROLE, &alice, &0);
+        rbac.grant_role(&admin, &TREASURY_ROLE, &bob, &0);
+
+        let target = Address::generate(&env);
+        let contexts = vec![&env, call_context(&env, &target, "transfer")];
+        let signers = vec![&env, alice.clone(), bob.clone()];
+
+        // What the account asks each signer to authorize
+        let sign_payload = MockAuthInvoke {
+            contract: &account.address,
+            fn_name: "__check_auth",
+            args: (payload(&env),).into_val(&env),
+            sub_invokes: &[],
+        };
+
+        // Both signers authorize the payload
+        env.mock_auths(&[
+            MockAuth {
+                address: &alice,
+                invoke: &sign_payload,
+            },
+            MockAuth {
+                address: &bob,
+                invoke: &sign_payload,
+            },
+        ]);
+        assert_eq!(
+            check_auth(&env, &account.address, &signers, &contexts),
+            Ok(())
+        );
+
+        // bob holds the signer role but did not sign
+        env.mock_auths(&[MockAuth {
+            address: &alice,
+            invoke: &sign_payload,
+        }]);
+        assert!(check_auth(&env, &account.address, &signers, &contexts).is_err());
+
+        env.set_auths(&[]);
+        assert!(check_auth(&env, &account.address, &signers, &contexts).is_err());
+    }
+
+    #[test]
+    fn test_revoked_signer_loses_access() {
+        let (env, admin, rbac, account) = setup();
+
+        let member = Address::generate(&env);
+        rbac.grant_role(&admin, &TREASURY_ROLE, &member, &0);
+        rbac.revoke_role(&admin, &TREASURY_ROLE, &member);
+
+        let target = Address::generate(&env);
+        let contexts = vec![&env, call_context(&env, &target, "transfer")];
+        assert_eq!(
+            check_auth(&env, &account.address, &vec![&env, member], &contexts),
+            Err(Ok(AccountError::SignerNotAuthorized))
+        );
+    }
+
+    #[test]
+    fn test_permission_requires_mapped_role() {
+        let (env, admin, rbac, account) = setup();
+
+        let target = Address::generate(&env);
+        let payout_fn = Symbol::new(&env, "payout");
+        account.set_permission(&target, &payout_fn, &PAYOUT_ROLE);
+        assert_eq!(
+            account.get_permission(&target, &payout_fn),
+            Some(PAYOUT_ROLE)
+        );
+
+        let member = Address::generate(&env);
+        rbac.grant_role(&admin, &TREASURY_ROLE, &member, &0);
+        let contexts = vec![&env, call_context(&env, &target, "payout")];
+
+        // Signer role alone is not enough for a mapped call
+        assert_eq!(
+            check_auth(
+                &env,
+                &account.address,
+                &vec![&env, member.clone()],
+                &contexts
+            ),
+            Err(Ok(AccountError::CallNotPermitted))
+        );
+
+        // Unmapped functions on the same contract still only need the signer role
+        let other = vec![&env, call_context(&env, &target, "transfer")];
+        assert_eq!(
+            check_auth(&env, &account.address, &vec![&env, member.clone()], &other),
+            Ok(())
+        );
+
+        rbac.grant_role(&admin, &PAYOUT_ROLE, &member, &0);
+        assert_eq!(
+            check_auth(
+                &env,
+                &account.address,
+                &vec![&env, member.clone()],
+                &contexts
+            ),
+            Ok(())
+        );
+
+        account.remove_permission(&target, &payout_fn);
+        assert_eq!(account.get_permission(&target, &payout_fn), None);
+    }
+
+    #[test]
+    fn test_deployment_is_rejected() {
+        let (env, admin, rbac, account) = setup();
+
+        let member = Address::generate(&env);
+        rbac.grant_role(&admin, &TREASURY_ROLE, &member, &0);
+
+        let deploy = Context::CreateContractHostFn(CreateContractHostFnContext {
+            executable: ContractExecutable::Wasm(BytesN::from_array(&env, &[2; 32])),
+            salt: BytesN::from_array(&env, &[3; 32]),
+        });
+        let contexts = vec![&env, deploy];
+        assert_eq!(
+            check_auth(&env, &account.address, &vec![&env, member], &contexts),
+            Err(Ok(AccountError::DeployNotPermitted))
+        );
+    }
+
+    #[test]
+    fn test_getters() {
+        let (_env, _admin, rbac, account) = setup();
+
+        assert_eq!(account.get_rbac_address(), rbac.address);
+        assert_eq!(account.get_signer_role(), TREASURY_ROLE);
+    }
+}
@@ -0,0 +1,119 @@
+//! Role checks cached in the calling contract's temporary storage.
+//!
+//! [`has_role_cached`] stores a positive `has_role` result for a bounded number of
+//! ledgers, together with the RBAC contract's revocation epoch. Every lookup still
+//! reads the epoch (a single storage read in RBAC), and any revoke, renounce,
+//! re-grant, delegation revocation, federated source removal or function mapping
+//! change invalidates all cached results at once, so a revocation is never masked.
+//!
+//! An entry is never used at or past the expiry of the grant or delegation that
+//! satisfied the check. Results satisfied only through a federated source are not
+//! cached, since a revocation in the source contract does not bump this epoch.
+//! Negative results are never cached.
+
+use soroban_sdk::{contracttype, Address, Env, Symbol};
+
+use crate::{RbacClient, RoleGrant};
+
+/// Temporary storage keys used by the cache.
+#[contracttype(export = false)]
+#[derive(Clone)]
+pub enum CacheKey {
+    /// Maps (RBAC contract, role, account) to a `CachedRole`
+    HasRole(Address, Symbol, Address),
+}
+
+/// A cached positive role check.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CachedRole {
+    /// RBAC revocation epoch when the check was made
+    pub epoch: u64,
+    /// Last ledger sequence at which the result may be used
+    pub valid_until: u32,
+    /// Expiry timestamp of the grant or delegation behind the result (0 = never)
+    pub expiry: u64,
+}
+
+/// `has_role` on the RBAC contract at `rbac`, reusing a positive result for up to
+/// `ttl_ledgers` ledgers while the revocation epoch is unchanged and the grant or
+/// delegation behind it has not expired.
+///
+/// `ttl_ledgers` 0 disables caching.
+pub fn has_role_cached(
+    env: &Env,
+    rbac: &Address,
+    role: &Symbol,
+    account: &Address,
+    ttl_ledgers: u32,
+) -> bool {
+    let client = RbacClient::new(env, rbac);
+    let key = CacheKey::HasRole(rbac.clone(), role.clone(), account.clone());
+    let epoch = client.get_revocation_epoch();
+    let sequence = env.ledger().sequence();
+    let now = env.ledger().timestamp();
+
+    if let Some(cached) = env.storage().temporary().get::<_, CachedRole>(&key) {
+        if cached.epoch == epoch
+            && sequence <= cached.valid_until
+            && (cached.expiry == 0 || now < cached.expiry)
+        {
+            return true;
+        }
+    }
+
+    // Only a local grant or delegation is cached; its expiry bounds the entry
+    if !client.has_local_role(role, account) {
+        env.storage().temporary().remove(&key);
+        return client.has_role(role, account);
+    }
+    if ttl_ledgers > 0 {
+        let expiry = match client.get_role_grant(role, account) {
+            Some(grant) if is_active(&grant, now) => grant.expiry,
+            // Otherwise the role is held through a delegation
+            _ => client
+                .get_delegation(role, account)
+                .map_or(0, |delegation| delegation.expiry),
+        };
+        let cached = CachedRole {
+            epoch,
+            valid_until: sequence.saturating_add(ttl_ledgers),
+            expiry,
+        };
+        env.storage().temporary().set(&key, &cached);
+        env.storage()
+            .temporary()
+            .extend_ttl(&key, ttl_ledgers, ttl_ledgers);
+    } else {
+        env.storage().temporary().remove(&key);
+    }
+    true
+}
+
+/// Whether a grant is active at `now`.
+fn is_active(grant: &RoleGrant, now: u64) -> bool {
+    grant.valid_from <= now && (grant.expiry == 0 || now < grant.expiry)
+}
+
+/// Drop a cached result, e.g. after the consumer learns of a change out of band.
+pub fn invalidate(env: &Env, rbac: &Address, role: &Symbol, account: &Address) {
+    env.storage().temporary().remove(&CacheKey::HasRole(
+        rbac.clone(),
+        role.clone(),
+        account.clone(),
+    ));
+}
+
+/// The cached result for `(rbac, role, account)`, if any and not yet evicted.
+pub fn get_cached(
+    env: &Env,
+    rbac: &Address,
+    role: &Symbol,
+    account: &Address,
+) -> Option<CachedRole> {
+    env.storage().temporary().get(&CacheKey::HasRole(
+        rbac.clone(),
+        role.clone(),
+        account.clone(),
+    ))
+}
@@ -0,0 +1,152 @@
+//! Mirrors of the RBAC contract's event structs.
+//!
+//! Each struct lists the fields of one event. On chain the first fields after the
+//! event name are published as topics and the rest as data (see `rbac/src/events.rs`).
+
+use crate::RolePolicy;
+use soroban_sdk::{contracttype, Address, Symbol};
+
+/// Event emitted when a new role is created.
+#[contracttype(export = false)]
+#[derive(Clone, Debug)]
+pub struct RoleCreatedEvent {
+    pub role: Symbol,
+    pub admin_role: Symbol,
+}
+
+/// Event emitted when a role's admin is changed.
+#[contracttype(export = false)]
+#[derive(Clone, Debug)]
+pub struct RoleAdminChangedEvent {
+    pub role: Symbol,
+    pub previous_admin: Symbol,
+    pub new_admin: Symbol,
+}
+
+/// Event emitted when a role is granted to an account.
+#[contracttype(export = false)]
+#[derive(Clone, Debug)]
+pub struct RoleGrantedEvent {
+    pub role: Symbol,
+    pub account: Address,
+    pub expiry: u64,
+    pub granted_by: Address,
+}
+
+/// Event emitted when a future-dated role grant is scheduled.
+#[contracttype(export = false)]
+#[derive(Clone, Debug)]
+pub struct RoleScheduledEvent {
+    pub role: Symbol,
+    pub account: Address,
+    pub valid_from: u64,
+    pub expiry: u64,
+    pub granted_by: Address,
+}
+
+/// Event emitted when an existing role grant is extended.
+#[contracttype(export = false)]
+#[derive(Clone, Debug)]
+pub struct RoleExtendedEvent {
+    pub role: Symbol,
+    pub account: Address,
+    pub old_expiry: u64,
+    pub new_expiry: u64,
+    pub extended_by: Address,
+}
+
+/// Event emitted when a role is revoked from an account.
+#[contracttype(export = false)]
+#[derive(Clone, Debug)]
+pub struct RoleRevokedEvent {
+    pub role: Symbol,
+    pub account: Address,
+    pub revoked_by: Address,
+}
+
+/// Event emitted when a role expires during an access check.
+#[contracttype(export = false)]
+#[derive(Clone, Debug)]
+pub struct RoleExpiredEvent {
+    pub role: Symbol,
+    pub account: Address,
+    pub expired_at: u64,
+}
+
+/// Event emitted when a role's grant policy is changed.
+#[contracttype(export = false)]
+#[derive(Clone, Debug)]
+pub struct RolePolicyChangedEvent {
+    pub role: Symbol,
+    pub policy: RolePolicy,
+}
+
+/// Event emitted when two roles are made mutually exclusive.
+#[contracttype(export = false)]
+#[derive(Clone, Debug)]
+pub struct RoleConflictAddedEvent {
+    pub role_a: Symbol,
+    pub role_b: Symbol,
+}
+
+/// Event emitted when a mutual-exclusion constraint is removed.
+#[contracttype(export = false)]
+#[derive(Clone, Debug)]
+pub struct RoleConflictRemovedEvent {
+    pub role_a: Symbol,
+    pub role_b: Symbol,
+}
+
+/// Event emitted when a role is mapped to a contract function.
+#[contracttype(export = false)]
+#[derive(Clone, Debug)]
+pub struct FunctionRoleSetEvent {
+    pub target: Address,
+    pub fn_name: Symbol,
+    pub role: Symbol,
+}
+
+/// Event emitted when a contract function's role mapping is removed.
+#[contracttype(export = false)]
+#[derive(Clone, Debug)]
+pub struct FunctionRoleRemovedEvent {
+    pub target: Address,
+    pub fn_name: Symbol,
+}
+
+/// Event emitted when a role holder delegates the role.
+#[contracttype(export = false)]
+#[derive(Clone, Debug)]
+pub struct RoleDelegatedEvent {
+    pub role: Symbol,
+    pub delegator: Address,
+    pub delegate: Address,
+    pub expiry: u64,
+}
+
+/// Event emitted when a delegation is revoked, including when its delegator loses the role.
+#[contracttype(export = false)]
+#[derive(Clone, Debug)]
+pub struct DelegationRevokedEvent {
+    pub role: Symbol,
+    pub delegator: Address,
+    pub delegate: Address,
+}
+
+/// Event emitted when a role in an external RBAC contract is trusted to satisfy a local role.
+#[contracttype(export = false)]
+#[derive(Clone, Debug)]
+pub struct FederatedRoleAddedEvent {
+    pub role: Symbol,
+    pub source_rbac: Address,
+    pub source_role: Symbol,
+}
+
+/// Event emitted when a federated source is removed from a local role.
+#[contracttype(export = false)]
+#[derive(Clone, Debug)]
+pub struct FederatedRoleRemovedEvent {
+    pub role: Symbol,
+    pub source_rbac: Address,
+    pub source_role: Symbol,
+}
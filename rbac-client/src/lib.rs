@@ -0,0 +1,396 @@
+//! # Stellar Keystone RBAC Client
+//!
+//! Typed client for contracts that call a deployed `RbacContract`.
+//!
+//! ## Contents
+//! - [`RbacClient`] - Generated client for every `RbacContract` entry point
+//! - [`RbacError`] - Mirror of the contract's error codes
+//! - [`RolePolicy`], [`RoleGrant`], [`Delegation`], [`FederatedRole`] - Mirrors of the contract's return types
+//! - [`events`] - Mirrors of the contract's event structs
+//! - [`cache`] - Role checks cached in the consumer's temporary storage
+//! - [`DEFAULT_ADMIN_ROLE`] - The reserved admin role symbol
+//! - [`authorize_check_call`] - Lets a contract role holder pass a nested `check_call`
+//!
+//! The mirrors are declared with `export = false`, so depending on this crate does not
+//! add RBAC types to the consumer's contract spec.
+//!
+//! ## Usage
+//! ```ignore
+//! use stellar_keystone_rbac_client::RbacClient;
+//!
+//! let rbac = RbacClient::new(&env, &rbac_address);
+//! if !rbac.has_role(&symbol_short!("WITHDRAW"), &caller) {
+//!     return Err(VaultError::NotAuthorized);
+//! }
+//! ```
+//!
+//! `tests/interface.rs` compares this interface with the contract's and fails to
+//! build if an entry point is missing on either side.
+
+#![no_std]
+
+pub mod cache;
+pub mod events;
+
+use soroban_sdk::auth::{ContractContext, InvokerContractAuthEntry, SubContractInvocation};
+use soroban_sdk::{
+    contractclient, contracterror, contractspecfn, contracttype, symbol_short, vec, Address, Env,
+    IntoVal, Symbol, Vec,
+};
+
+/// The default admin role symbol. Matches `stellar_keystone_rbac::DEFAULT_ADMIN_ROLE`.
+pub const DEFAULT_ADMIN_ROLE: Symbol = symbol_short!("DEF_ADMIN");
+
+/// Mirror of `stellar_keystone_rbac::RbacError`.
+#[contracterror(export = false)]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum RbacError {
+    /// caller doesnt have required auth role
+    NotAuthorized = 1,
+    /// role expired
+    InvalidExpiry = 2,
+    /// role not found
+    RoleNotFound = 3,
+    /// acc not authorized
+    NotMember = 4,
+    /// role already exists
+    RoleAlreadyExists = 5,
+    /// cannot set role as its own admin (except DEFAULT_ADMIN_ROLE)
+    InvalidSelfAdmin = 6,
+    /// contract already initialized
+    AlreadyInitialized = 7,
+    /// internal storage state is corrupted
+    StorageCorrupted = 8,
+    /// grant is open-ended or longer than the role's max duration
+    ExceedsMaxDuration = 9,
+    /// account holds a role that is mutually exclusive with the one being granted
+    ConflictingRole = 10,
+    /// a role cannot conflict with itself
+    InvalidRoleConflict = 11,
+    /// role already has its maximum number of members
+    MaxMembersReached = 12,
+    /// no role is mapped to the called function
+    FunctionNotMapped = 13,
+    /// an account cannot delegate a role to itself
+    InvalidDelegation = 14,
+    /// no delegation of the role from this delegator
+    DelegationNotFound = 15,
+    /// a role cannot federate to this contract itself
+    InvalidFederation = 16,
+    /// role already has the maximum number of federated sources
+    TooManyFederatedSources = 17,
+    /// address is not a deployed Wasm contract
+    NotAContract = 18,
+    /// the two roles have no conflict configured
+    RoleConflictNotFound = 19,
+    /// delegator already has the maximum number of delegates for the role
+    TooManyDelegates = 20,
+    /// the source is not federated for the role
+    FederationNotFound = 21,
+}
+
+/// Mirror of `stellar_keystone_rbac::RolePolicy`.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct RolePolicy {
+    /// Maximum grant lifetime in seconds (0 = unrestricted)
+    pub max_duration: u64,
+    /// Maximum number of accounts holding the role (0 = unrestricted)
+    pub max_members: u32,
+}
+
+/// Mirror of `stellar_keystone_rbac::RoleGrant`.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RoleGrant {
+    /// Activation timestamp (0 = active immediately)
+    pub valid_from: u64,
+    /// Expiry timestamp (0 = never expires)
+    pub expiry: u64,
+}
+
+/// Mirror of `stellar_keystone_rbac::Delegation`.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Delegation {
+    /// Account holding the role directly
+    pub delegator: Address,
+    /// Expiry timestamp, capped at the delegator's own expiry
+    pub expiry: u64,
+}
+
+/// Mirror of `stellar_keystone_rbac::FederatedRole`.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FederatedRole {
+    /// The external `RbacContract`
+    pub rbac: Address,
+    /// The role in that contract
+    pub role: Symbol,
+}
+
+/// Authorize the RBAC `check_call(target, fn_name, <current contract>)` that `target`
+/// makes while handling a call from the current contract.
+///
+/// A contract's auth is implicit only for calls it makes directly. When a keeper
+/// contract calls `vault.withdraw(keeper, ..)`, the vault's own `keeper.require_auth()`
+/// passes, but RBAC's check runs one level deeper and needs this entry. Call it
+/// right before invoking `target`.
+pub fn authorize_check_call(env: &Env, rbac: &Address, target: &Address, fn_name: &Symbol) {
+    let this = env.current_contract_address();
+    env.authorize_as_current_contract(vec![
+        env,
+        InvokerContractAuthEntry::Contract(SubContractInvocation {
+            context: ContractContext {
+                contract: rbac.clone(),
+                fn_name: Symbol::new(env, "check_call"),
+                args: (target.clone(), fn_name.clone(), this).into_val(env),
+            },
+            sub_invocations: Vec::new(env),
+        }),
+    ]);
+}
+
+/// Holds the spec of [`RbacInterface`], used to check it against the contract.
+pub struct RbacSpec;
+
+/// The `RbacContract` interface. See `rbac/src/lib.rs` for the full documentation
+/// of each entry point.
+#[contractspecfn(name = "RbacSpec", export = false)]
+#[contractclient(name = "RbacClient")]
+pub trait RbacInterface {
+    // Setup and role management
+
+    /// Initialize the contract and grant `DEFAULT_ADMIN_ROLE` to `admin`.
+    fn initialize(env: Env, admin: Address) -> Result<(), RbacError>;
+
+    /// Create a role administered by `admin_role`.
+    fn create_role(
+        env: Env,
+        caller: Address,
+        role: Symbol,
+        admin_role: Symbol,
+    ) -> Result<(), RbacError>;
+
+    /// Change the admin role of `role`.
+    fn set_role_admin(
+        env: Env,
+        caller: Address,
+        role: Symbol,
+        admin_role: Symbol,
+    ) -> Result<(), RbacError>;
+
+    /// Set the maximum grant lifetime for a role (0 = unrestricted).
+    fn set_role_max_duration(
+        env: Env,
+        caller: Address,
+        role: Symbol,
+        max_duration: u64,
+    ) -> Result<(), RbacError>;
+
+    /// Set the maximum number of accounts holding a role (0 = unrestricted).
+    fn set_role_max_members(
+        env: Env,
+        caller: Address,
+        role: Symbol,
+        max_members: u32,
+    ) -> Result<(), RbacError>;
+
+    /// Make two roles mutually exclusive.
+    fn add_role_conflict(
+        env: Env,
+        caller: Address,
+        role_a: Symbol,
+        role_b: Symbol,
+    ) -> Result<(), RbacError>;
+
+    /// Remove a mutual-exclusion constraint.
+    fn remove_role_conflict(
+        env: Env,
+        caller: Address,
+        role_a: Symbol,
+        role_b: Symbol,
+    ) -> Result<(), RbacError>;
+
+    // Function policies
+
+    /// Require `role` for calls to `target.fn_name`.
+    fn set_function_role(
+        env: Env,
+        caller: Address,
+        target: Address,
+        fn_name: Symbol,
+        role: Symbol,
+    ) -> Result<(), RbacError>;
+
+    /// Remove the role mapping for `target.fn_name`.
+    fn remove_function_role(
+        env: Env,
+        caller: Address,
+        target: Address,
+        fn_name: Symbol,
+    ) -> Result<(), RbacError>;
+
+    // Federation
+
+    /// Let `source_role` in a trusted external RBAC contract satisfy `role`.
+    fn add_federated_role(
+        env: Env,
+        caller: Address,
+        role: Symbol,
+        source_rbac: Address,
+        source_role: Symbol,
+    ) -> Result<(), RbacError>;
+
+    /// Stop accepting `source_role` from an external RBAC contract for `role`.
+    fn remove_federated_role(
+        env: Env,
+        caller: Address,
+        role: Symbol,
+        source_rbac: Address,
+        source_role: Symbol,
+    ) -> Result<(), RbacError>;
+
+    // Role grants
+
+    /// Grant a role, active immediately (`expiry` 0 = never expires).
+    fn grant_role(
+        env: Env,
+        caller: Address,
+        role: Symbol,
+        account: Address,
+        expiry: u64,
+    ) -> Result<(), RbacError>;
+
+    /// Grant a role that becomes active at `valid_from`.
+    fn grant_role_scheduled(
+        env: Env,
+        caller: Address,
+        role: Symbol,
+        account: Address,
+        valid_from: u64,
+        expiry: u64,
+    ) -> Result<(), RbacError>;
+
+    /// Grant a role to a deployed contract (`Err(NotAContract)` otherwise).
+    fn grant_role_to_contract(
+        env: Env,
+        caller: Address,
+        role: Symbol,
+        contract: Address,
+        expiry: u64,
+    ) -> Result<(), RbacError>;
+
+    /// Push back the expiry of an existing grant.
+    fn extend_role(
+        env: Env,
+        caller: Address,
+        role: Symbol,
+        account: Address,
+        new_expiry: u64,
+    ) -> Result<(), RbacError>;
+
+    /// Revoke a role from an account.
+    fn revoke_role(
+        env: Env,
+        caller: Address,
+        role: Symbol,
+        account: Address,
+    ) -> Result<(), RbacError>;
+
+    /// Give up a role held by `account`.
+    fn renounce_role(env: Env, account: Address, role: Symbol) -> Result<(), RbacError>;
+
+    // Delegation
+
+    /// Temporarily share a directly held role with `delegate`.
+    fn delegate_role(
+        env: Env,
+        delegator: Address,
+        role: Symbol,
+        delegate: Address,
+        expiry: u64,
+    ) -> Result<(), RbacError>;
+
+    /// Withdraw a delegation before it expires.
+    fn revoke_delegation(
+        env: Env,
+        delegator: Address,
+        role: Symbol,
+        delegate: Address,
+    ) -> Result<(), RbacError>;
+
+    // Role checks
+
+    /// `true` if `account` currently holds `role`.
+    fn has_role(env: Env, role: Symbol, account: Address) -> bool;
+
+    /// `true` if `account` holds `role` in this contract, ignoring federation.
+    fn has_local_role(env: Env, role: Symbol, account: Address) -> bool;
+
+    /// Remove an expired grant. `true` if one was removed.
+    fn cleanup_expired_role(env: Env, role: Symbol, account: Address) -> bool;
+
+    /// Remove an expired delegation. `true` if one was removed.
+    fn cleanup_expired_delegation(env: Env, role: Symbol, delegate: Address) -> bool;
+
+    /// `Err(NotAuthorized)` unless `account` currently holds `role`.
+    fn require_role(env: Env, role: Symbol, account: Address) -> Result<(), RbacError>;
+
+    /// Require `account`'s signature, then `Err(NotAuthorized)` unless it holds `role`.
+    fn authorize(env: Env, role: Symbol, account: Address) -> Result<(), RbacError>;
+
+    /// Require `account`'s signature, then `Err(NotAuthorized)` unless it holds the role
+    /// mapped to `target.fn_name` (`Err(FunctionNotMapped)` if there is none).
+    fn check_call(
+        env: Env,
+        target: Address,
+        fn_name: Symbol,
+        account: Address,
+    ) -> Result<(), RbacError>;
+
+    // Getters
+
+    /// Expiry of a grant (0 = never expires, or not a member).
+    fn get_role_expiry(env: Env, role: Symbol, account: Address) -> u64;
+
+    /// Both bounds of a grant, or `None` if not a member.
+    fn get_role_grant(env: Env, role: Symbol, account: Address) -> Option<RoleGrant>;
+
+    /// Number of stored grants for a role.
+    fn get_role_member_count(env: Env, role: Symbol) -> u32;
+
+    /// Roles that are mutually exclusive with `role`.
+    fn get_role_conflicts(env: Env, role: Symbol) -> Vec<Symbol>;
+
+    /// Roles held by `account` that conflict with `role`.
+    fn get_conflicting_roles(env: Env, role: Symbol, account: Address) -> Vec<Symbol>;
+
+    /// Delegation of `role` held by `delegate`, if any.
+    fn get_delegation(env: Env, role: Symbol, delegate: Address) -> Option<Delegation>;
+
+    /// External sources that can satisfy `role`.
+    fn get_federated_roles(env: Env, role: Symbol) -> Vec<FederatedRole>;
+
+    /// Role mapped to `target.fn_name`, if any.
+    fn get_function_role(env: Env, target: Address, fn_name: Symbol) -> Option<Symbol>;
+
+    /// Admin role of `role`.
+    fn get_role_admin(env: Env, role: Symbol) -> Symbol;
+
+    /// Grant policy of `role`.
+    fn get_role_policy(env: Env, role: Symbol) -> RolePolicy;
+
+    /// `true` if `role` has been created.
+    fn role_exists(env: Env, role: Symbol) -> bool;
+
+    /// Address that initialized the contract.
+    fn get_deployer(env: Env) -> Option<Address>;
+
+    /// Counter bumped whenever a role holder may lose a role.
+    fn get_revocation_epoch(env: Env) -> u64;
+
+    /// The `DEFAULT_ADMIN_ROLE` symbol.
+    fn default_admin_role(env: Env) -> Symbol;
+}
@@ -0,0 +1,176 @@
+#![cfg(test)]
+
+//! `cache::has_role_cached` from a consumer contract.
+
+use soroban_sdk::testutils::{Address as _, Ledger};
+use soroban_sdk::{contract, contractimpl, symbol_short, Address, Env, Symbol};
+use stellar_keystone_rbac::{RbacContract, RbacContractClient};
+use stellar_keystone_rbac_client::cache::{self, CachedRole};
+
+const ROLE: Symbol = symbol_short!("WITHDRAW");
+const TTL: u32 = 100;
+
+#[contract]
+struct Consumer;
+
+#[contractimpl]
+impl Consumer {
+    pub fn check(env: Env, rbac: Address, account: Address, ttl_ledgers: u32) -> bool {
+        cache::has_role_cached(&env, &rbac, &ROLE, &account, ttl_ledgers)
+    }
+
+    pub fn cached(env: Env, rbac: Address, account: Address) -> Option<CachedRole> {
+        cache::get_cached(&env, &rbac, &ROLE, &account)
+    }
+
+    pub fn invalidate(env: Env, rbac: Address, account: Address) {
+        cache::invalidate(&env, &rbac, &ROLE, &account);
+    }
+}
+
+fn setup() -> (
+    Env,
+    Address,
+    RbacContractClient<'static>,
+    ConsumerClient<'static>,
+) {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let rbac = RbacContractClient::new(&env, &env.register(RbacContract, ()));
+    let admin = Address::generate(&env);
+    rbac.initialize(&admin);
+    rbac.create_role(&admin, &ROLE, &rbac.default_admin_role());
+
+    let consumer = ConsumerClient::new(&env, &env.register(Consumer, ()));
+    (env, admin, rbac, consumer)
+}
+
+fn advance_ledgers(env: &Env, ledgers: u32) {
+    env.ledger().with_mut(|li| li.sequence_number += ledgers);
+}
+
+#[test]
+fn test_positive_check_is_cached() {
+    let (env, admin, rbac, consumer) = setup();
+    let account = Address::generate(&env);
+    rbac.grant_role(&admin, &ROLE, &account, &0);
+
+    let sequence = env.ledger().sequence();
+    assert!(consumer.check(&rbac.address, &account, &TTL));
+    assert_eq!(
+        consumer.cached(&rbac.address, &account),
+        Some(CachedRole {
+            epoch: 0,
+            valid_until: sequence + TTL,
+            expiry: 0,
+        })
+    );
+
+    // Unrelated grants keep the epoch, so the entry stays valid
+    rbac.grant_role(&admin, &ROLE, &Address::generate(&env), &0);
+    advance_ledgers(&env, TTL / 2);
+    assert!(consumer.check(&rbac.address, &account, &TTL));
+    assert_eq!(
+        consumer
+            .cached(&rbac.address, &account)
+            .unwrap()
+            .valid_until,
+        sequence + TTL
+    );
+
+    consumer.invalidate(&rbac.address, &account);
+    assert_eq!(consumer.cached(&rbac.address, &account), None);
+}
+
+#[test]
+fn test_revocation_is_never_masked() {
+    let (env, admin, rbac, consumer) = setup();
+    let account = Address::generate(&env);
+    rbac.grant_role(&admin, &ROLE, &account, &0);
+    assert!(consumer.check(&rbac.address, &account, &TTL));
+
+    rbac.revoke_role(&admin, &ROLE, &account);
+    assert!(!consumer.check(&rbac.address, &account, &TTL));
+    assert_eq!(consumer.cached(&rbac.address, &account), None);
+
+    // A revocation of another account also invalidates, then the entry is rebuilt
+    rbac.grant_role(&admin, &ROLE, &account, &0);
+    assert!(consumer.check(&rbac.address, &account, &TTL));
+    let other = Address::generate(&env);
+    rbac.grant_role(&admin, &ROLE, &other, &0);
+    rbac.revoke_role(&admin, &ROLE, &other);
+    assert!(consumer.check(&rbac.address, &account, &TTL));
+    assert_eq!(
+        consumer.cached(&rbac.address, &account).unwrap().epoch,
+        rbac.get_revocation_epoch()
+    );
+}
+
+#[test]
+fn test_cached_result_ends_at_grant_expiry() {
+    let (env, admin, rbac, consumer) = setup();
+    let account = Address::generate(&env);
+    let now = env.ledger().timestamp();
+    rbac.grant_role(&admin, &ROLE, &account, &(now + 10));
+    assert!(consumer.check(&rbac.address, &account, &TTL));
+    assert_eq!(
+        consumer.cached(&rbac.address, &account).unwrap().expiry,
+        now + 10
+    );
+
+    // Expiry does not bump the epoch, but the entry is not used past it
+    env.ledger().with_mut(|li| li.timestamp = now + 10);
+    assert!(!rbac.has_role(&ROLE, &account));
+    assert!(!consumer.check(&rbac.address, &account, &TTL));
+    assert_eq!(consumer.cached(&rbac.address, &account), None);
+}
+
+#[test]
+fn test_cached_result_ends_at_delegation_expiry() {
+    let (env, admin, rbac, consumer) = setup();
+    let delegator = Address::generate(&env);
+    let delegate = Address::generate(&env);
+    let now = env.ledger().timestamp();
+    rbac.grant_role(&admin, &ROLE, &delegator, &0);
+    rbac.delegate_role(&delegator, &ROLE, &delegate, &(now + 10));
+    assert!(consumer.check(&rbac.address, &delegate, &TTL));
+    assert_eq!(
+        consumer.cached(&rbac.address, &delegate).unwrap().expiry,
+        now + 10
+    );
+
+    env.ledger().with_mut(|li| li.timestamp = now + 10);
+    assert!(!consumer.check(&rbac.address, &delegate, &TTL));
+}
+
+#[test]
+fn test_federated_result_is_not_cached() {
+    let (env, admin, rbac, consumer) = setup();
+    let source = RbacContractClient::new(&env, &env.register(RbacContract, ()));
+    source.initialize(&admin);
+    source.create_role(&admin, &ROLE, &source.default_admin_role());
+    rbac.add_federated_role(&admin, &ROLE, &source.address, &ROLE);
+
+    let account = Address::generate(&env);
+    source.grant_role(&admin, &ROLE, &account, &0);
+    assert!(consumer.check(&rbac.address, &account, &TTL));
+    assert_eq!(consumer.cached(&rbac.address, &account), None);
+
+    // A revocation in the source takes effect immediately
+    source.revoke_role(&admin, &ROLE, &account);
+    assert!(!consumer.check(&rbac.address, &account, &TTL));
+}
+
+#[test]
+fn test_negative_and_uncached_checks() {
+    let (env, admin, rbac, consumer) = setup();
+    let account = Address::generate(&env);
+
+    assert!(!consumer.check(&rbac.address, &account, &TTL));
+    assert_eq!(consumer.cached(&rbac.address, &account), None);
+
+    rbac.grant_role(&admin, &ROLE, &account, &0);
+    assert!(consumer.check(&rbac.address, &account, &0));
+    assert_eq!(consumer.cached(&rbac.address, &account), None);
+}
@@ -0,0 +1,359 @@
+#![cfg(test)]
+
+//! Checks that the client crate matches the RBAC contract it calls.
+//!
+//! Entry points are listed once in `ENTRY_POINTS`, and the function spec entries of
+//! `RbacSpec` and `RbacContract` are compared for each of them. Native builds do not
+//! embed the contract spec, so the function lists themselves are read from the items
+//! the spec macros expand: the `#[contractimpl]` block and the `#[contractclient]`
+//! trait. Error variants come from the contract's error spec entry.
+//!
+//! The mirrored storage and event structs are `export = false`, so they have no spec
+//! entries either. Their names, field names and field types are read from the sources
+//! of both crates and compared.
+
+use paste::paste;
+use soroban_sdk::testutils::Address as _;
+use soroban_sdk::xdr::{Limits, ReadXdr, ScSpecEntry, ScSpecTypeDef};
+use soroban_sdk::{symbol_short, Address, Env, IntoVal, TryFromVal, Val};
+use std::collections::{BTreeMap, BTreeSet};
+use stellar_keystone_rbac::RbacContract;
+use stellar_keystone_rbac_client::{
+    events, Delegation, FederatedRole, RbacClient, RbacError, RbacSpec, RoleGrant, RolePolicy,
+    DEFAULT_ADMIN_ROLE,
+};
+
+/// Function name, argument names and types, and return types, without docs.
+type Signature = (
+    String,
+    std::vec::Vec<(String, ScSpecTypeDef)>,
+    std::vec::Vec<ScSpecTypeDef>,
+);
+
+fn signature(xdr: &[u8]) -> Signature {
+    match ScSpecEntry::from_xdr(xdr, Limits::none()).unwrap() {
+        ScSpecEntry::FunctionV0(f) => (
+            f.name.to_utf8_string().unwrap(),
+            f.inputs
+                .iter()
+                .map(|i| (i.name.to_utf8_string().unwrap(), i.type_.clone()))
+                .collect(),
+            f.outputs.to_vec(),
+        ),
+        other => panic!("expected a function spec, got {other:?}"),
+    }
+}
+
+macro_rules! entry_points {
+    ($($name:ident),* $(,)?) => {
+        const ENTRY_POINTS: &[&str] = &[$(stringify!($name)),*];
+
+        /// (name, client signature, contract signature) for every entry point.
+        fn signature_pairs() -> std::vec::Vec<(&'static str, Signature, Signature)> {
+            paste! {
+                std::vec![$((
+                    stringify!($name),
+                    signature(&RbacSpec::[<spec_xdr_ $name>]()),
+                    signature(&RbacContract::[<spec_xdr_ $name>]()),
+                )),*]
+            }
+        }
+    };
+}
+
+entry_points!(
+    initialize,
+    create_role,
+    set_role_admin,
+    set_role_max_duration,
+    set_role_max_members,
+    add_role_conflict,
+    remove_role_conflict,
+    set_function_role,
+    remove_function_role,
+    add_federated_role,
+    remove_federated_role,
+    grant_role,
+    grant_role_scheduled,
+    grant_role_to_contract,
+    extend_role,
+    revoke_role,
+    renounce_role,
+    delegate_role,
+    revoke_delegation,
+    has_role,
+    has_local_role,
+    cleanup_expired_role,
+    cleanup_expired_delegation,
+    require_role,
+    authorize,
+    check_call,
+    get_role_expiry,
+    get_role_grant,
+    get_role_member_count,
+    get_role_conflicts,
+    get_conflicting_roles,
+    get_delegation,
+    get_federated_roles,
+    get_function_role,
+    get_role_admin,
+    get_role_policy,
+    role_exists,
+    get_deployer,
+    get_revocation_epoch,
+    default_admin_role,
+);
+
+/// Highest error code the client is checked for. Contract error codes are small and
+/// dense, so anything above this would be a typo.
+const MAX_ERROR_CODE: u32 = 255;
+
+/// Names of the `pub fn`s of the `#[contractimpl]` block, or of the `#[contractclient]`
+/// trait, in `source`. These are the items their macros emit function specs for.
+fn spec_fn_names(source: &str) -> BTreeSet<String> {
+    let has_attr =
+        |attrs: &[syn::Attribute], name: &str| attrs.iter().any(|attr| attr.path().is_ident(name));
+    let mut names = BTreeSet::new();
+    for item in syn::parse_file(source).unwrap().items {
+        match item {
+            syn::Item::Impl(block) if has_attr(&block.attrs, "contractimpl") => {
+                names.extend(block.items.iter().filter_map(|item| match item {
+                    syn::ImplItem::Fn(f) if matches!(f.vis, syn::Visibility::Public(_)) => {
+                        Some(f.sig.ident.to_string())
+                    }
+                    _ => None,
+                }));
+            }
+            syn::Item::Trait(client) if has_attr(&client.attrs, "contractclient") => {
+                names.extend(client.items.iter().filter_map(|item| match item {
+                    syn::TraitItem::Fn(f) => Some(f.sig.ident.to_string()),
+                    _ => None,
+                }));
+            }
+            _ => {}
+        }
+    }
+    names
+}
+
+/// Fields (name, type) of every `#[contracttype]` struct in `source`, by struct name.
+fn contracttype_structs(source: &str) -> BTreeMap<String, std::vec::Vec<(String, syn::Type)>> {
+    syn::parse_file(source)
+        .unwrap()
+        .items
+        .into_iter()
+        .filter_map(|item| match item {
+            syn::Item::Struct(s) if s.attrs.iter().any(|a| a.path().is_ident("contracttype")) => {
+                let fields = s
+                    .fields
+                    .iter()
+                    .map(|f| (f.ident.as_ref().unwrap().to_string(), f.ty.clone()))
+                    .collect();
+                Some((s.ident.to_string(), fields))
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+/// Fails with the entries found on only one side, if any.
+fn assert_same<T: Ord + core::fmt::Debug>(
+    a: &BTreeSet<T>,
+    b: &BTreeSet<T>,
+    a_name: &str,
+    b_name: &str,
+) {
+    let only_a: std::vec::Vec<&T> = a.difference(b).collect();
+    let only_b: std::vec::Vec<&T> = b.difference(a).collect();
+    assert!(
+        only_a.is_empty() && only_b.is_empty(),
+        "only in {a_name}: {only_a:?}, only in {b_name}: {only_b:?}"
+    );
+}
+
+#[test]
+fn test_entry_point_signatures_match() {
+    for (name, client, contract) in signature_pairs() {
+        assert_eq!(client, contract, "signature of `{name}` differs");
+    }
+}
+
+#[test]
+fn test_entry_points_match() {
+    let contract = spec_fn_names(include_str!("../../rbac/src/lib.rs"));
+    let client = spec_fn_names(include_str!("../src/lib.rs"));
+    let listed: BTreeSet<String> = ENTRY_POINTS.iter().map(|name| name.to_string()).collect();
+
+    assert_same(&contract, &client, "contract", "client");
+    assert_same(&contract, &listed, "contract", "ENTRY_POINTS");
+}
+
+#[test]
+fn test_error_codes_match() {
+    // (name, code) for every case of the contract's error spec
+    let contract: BTreeSet<(String, u32)> =
+        match ScSpecEntry::from_xdr(stellar_keystone_rbac::RbacError::spec_xdr(), Limits::none())
+            .unwrap()
+        {
+            ScSpecEntry::UdtErrorEnumV0(e) => e
+                .cases
+                .iter()
+                .map(|case| (case.name.to_utf8_string().unwrap(), case.value))
+                .collect(),
+            other => panic!("expected an error enum spec, got {other:?}"),
+        };
+
+    // (name, code) for every code the client decodes into a variant
+    let client: BTreeSet<(String, u32)> = (0..=MAX_ERROR_CODE)
+        .filter_map(|code| {
+            let error = soroban_sdk::Error::from_contract_error(code);
+            RbacError::try_from(error)
+                .ok()
+                .map(|e| (std::format!("{e:?}"), code))
+        })
+        .collect();
+
+    assert!(contract.iter().all(|(_, code)| *code <= MAX_ERROR_CODE));
+    assert_same(&contract, &client, "contract", "client");
+}
+
+#[test]
+fn test_mirrored_types_match() {
+    let pairs = [
+        (
+            "storage types",
+            include_str!("../../rbac/src/storage.rs"),
+            include_str!("../src/lib.rs"),
+        ),
+        (
+            "events",
+            include_str!("../../rbac/src/events.rs"),
+            include_str!("../src/events.rs"),
+        ),
+    ];
+    for (kind, contract_source, client_source) in pairs {
+        let contract = contracttype_structs(contract_source);
+        let client = contracttype_structs(client_source);
+        assert!(!contract.is_empty(), "no {kind} found");
+
+        let contract_names: BTreeSet<&String> = contract.keys().collect();
+        let client_names: BTreeSet<&String> = client.keys().collect();
+        assert_same(&contract_names, &client_names, "contract", "client");
+        for (name, fields) in &contract {
+            assert_eq!(&client[name], fields, "fields of `{name}` differ");
+        }
+    }
+}
+
+#[test]
+fn test_types_round_trip() {
+    let env = Env::default();
+
+    let policy = stellar_keystone_rbac::RolePolicy {
+        max_duration: 3600,
+        max_members: 2,
+    };
+    let val: Val = policy.into_val(&env);
+    assert_eq!(
+        RolePolicy::try_from_val(&env, &val).unwrap(),
+        RolePolicy {
+            max_duration: 3600,
+            max_members: 2
+        }
+    );
+
+    let grant = stellar_keystone_rbac::RoleGrant {
+        valid_from: 10,
+        expiry: 20,
+    };
+    let val: Val = grant.into_val(&env);
+    assert_eq!(
+        RoleGrant::try_from_val(&env, &val).unwrap(),
+        RoleGrant {
+            valid_from: 10,
+            expiry: 20
+        }
+    );
+
+    let account = Address::generate(&env);
+    let delegation = stellar_keystone_rbac::Delegation {
+        delegator: account.clone(),
+        expiry: 20,
+    };
+    let val: Val = delegation.into_val(&env);
+    assert_eq!(
+        Delegation::try_from_val(&env, &val).unwrap(),
+        Delegation {
+            delegator: account.clone(),
+            expiry: 20
+        }
+    );
+
+    let source = stellar_keystone_rbac::FederatedRole {
+        rbac: account.clone(),
+        role: symbol_short!("SEC"),
+    };
+    let val: Val = source.into_val(&env);
+    assert_eq!(
+        FederatedRole::try_from_val(&env, &val).unwrap(),
+        FederatedRole {
+            rbac: account.clone(),
+            role: symbol_short!("SEC")
+        }
+    );
+
+    let event = stellar_keystone_rbac::events::RoleScheduledEvent {
+        role: symbol_short!("WITHDRAW"),
+        account: account.clone(),
+        valid_from: 10,
+        expiry: 20,
+        granted_by: account.clone(),
+    };
+    let val: Val = event.into_val(&env);
+    let mirrored = events::RoleScheduledEvent::try_from_val(&env, &val).unwrap();
+    assert_eq!(mirrored.account, account);
+    assert_eq!(mirrored.valid_from, 10);
+
+    assert_eq!(
+        DEFAULT_ADMIN_ROLE,
+        stellar_keystone_rbac::DEFAULT_ADMIN_ROLE
+    );
+}
+
+#[test]
+fn test_client_against_deployed_contract() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let rbac_id = env.register(RbacContract, ());
+    let rbac = RbacClient::new(&env, &rbac_id);
+    let admin = Address::generate(&env);
+    let account = Address::generate(&env);
+    let role = symbol_short!("WITHDRAW");
+
+    rbac.initialize(&admin);
+    assert_eq!(rbac.default_admin_role(), DEFAULT_ADMIN_ROLE);
+
+    rbac.create_role(&admin, &role, &DEFAULT_ADMIN_ROLE);
+    rbac.set_role_max_members(&admin, &role, &1);
+    rbac.grant_role(&admin, &role, &account, &0);
+
+    assert!(rbac.has_role(&role, &account));
+    assert_eq!(
+        rbac.get_role_grant(&role, &account),
+        Some(RoleGrant {
+            valid_from: 0,
+            expiry: 0
+        })
+    );
+    assert_eq!(
+        rbac.get_role_policy(&role),
+        RolePolicy {
+            max_duration: 0,
+            max_members: 1
+        }
+    );
+
+    let result = rbac.try_grant_role(&admin, &role, &Address::generate(&env), &0);
+    assert_eq!(result, Err(Ok(RbacError::MaxMembersReached)));
+}
@@ -0,0 +1,220 @@
+//! # Stellar Keystone RBAC Macros
+//!
+//! Attribute macros that remove the role-check boilerplate from consumer contracts.
+//!
+//! ## `#[only_role]`
+//! ```ignore
+//! #[contractimpl]
+//! impl SecureVaultContract {
+//!     // Embedded mode: RBAC state lives in this contract's storage
+//!     #[only_role("WITHDRAW", caller)]
+//!     pub fn withdraw(env: Env, caller: Address, amount: i128) -> Result<(), RbacError> { .. }
+//!
+//!     // Cross-contract mode: ask a deployed RbacContract, with a custom error
+//!     #[only_role(WITHDRAW_ROLE, caller, rbac = Self::rbac_address(&env)?, err = VaultError::NotAuthorized)]
+//!     pub fn withdraw(env: Env, caller: Address, amount: i128) -> Result<(), VaultError> { .. }
+//! }
+//! ```
+//!
+//! The guard runs before the function body:
+//! 1. `caller.require_auth()`
+//! 2. `has_role(role, caller)`, either via `stellar_keystone_rbac::roles` (embedded) or a
+//!    `has_role` call on the RBAC contract at `rbac` (cross-contract)
+//! 3. `return Err(err)` if the role is missing, or if the cross-contract call fails (e.g.
+//!    `rbac` is not an RBAC contract)
+//!
+//! ## Arguments
+//! - **role** — a string literal is a symbol name (`"WITHDRAW"` → `Symbol::new(&env, "WITHDRAW")`),
+//!   checked at compile time; anything else, including a bare identifier such as a
+//!   `WITHDRAW_ROLE` const, is an expression that must evaluate to a `Symbol`
+//! - **caller** — the `Address` parameter to authenticate and check
+//! - **rbac = expr** (optional) — `Address` of a deployed `RbacContract`; enables cross-contract mode
+//! - **err = expr** — error returned on failure. Required in cross-contract mode, so the
+//!   consumer does not depend on the contract crate; in embedded mode it defaults to
+//!   `stellar_keystone_rbac::RbacError::NotAuthorized` converted with `From`
+//!
+//! The guarded function must take an `Env` parameter and return a `Result`.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::parse::{Parse, ParseStream};
+use syn::{parse_macro_input, Expr, ExprLit, FnArg, Ident, ItemFn, Lit, Pat, Token, Type};
+
+/// Longest name a `Symbol` can hold.
+const MAX_SYMBOL_LEN: usize = 32;
+
+/// Guard a contract function with an RBAC role check. See the crate docs for arguments.
+#[proc_macro_attribute]
+pub fn only_role(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let args = parse_macro_input!(attr as OnlyRoleArgs);
+    let func = parse_macro_input!(item as ItemFn);
+
+    match expand(args, func) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+// =============================================================================
+// Argument Parsing
+// =============================================================================
+
+/// Parsed `#[only_role(role, caller, rbac = .., err = ..)]` arguments.
+struct OnlyRoleArgs {
+    role: Expr,
+    caller: Ident,
+    rbac: Option<Expr>,
+    err: Option<Expr>,
+}
+
+impl Parse for OnlyRoleArgs {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let role: Expr = input.parse()?;
+        input.parse::<Token![,]>()?;
+        let caller: Ident = input.parse()?;
+
+        let mut rbac = None;
+        let mut err = None;
+        while !input.is_empty() {
+            input.parse::<Token![,]>()?;
+            if input.is_empty() {
+                break;
+            }
+
+            let key: Ident = input.parse()?;
+            input.parse::<Token![=]>()?;
+            let value: Expr = input.parse()?;
+            let slot = match key.to_string().as_str() {
+                "rbac" => &mut rbac,
+                "err" => &mut err,
+                _ => {
+                    return Err(syn::Error::new(
+                        key.span(),
+                        "unknown option, expected `rbac` or `err`",
+                    ))
+                }
+            };
+            if slot.replace(value).is_some() {
+                return Err(syn::Error::new(key.span(), "option specified twice"));
+            }
+        }
+
+        if let (Some(rbac), None) = (&rbac, &err) {
+            return Err(syn::Error::new_spanned(
+                rbac,
+                "`err` is required when `rbac` is given",
+            ));
+        }
+
+        Ok(OnlyRoleArgs {
+            role,
+            caller,
+            rbac,
+            err,
+        })
+    }
+}
+
+// =============================================================================
+// Expansion
+// =============================================================================
+
+fn expand(args: OnlyRoleArgs, mut func: ItemFn) -> syn::Result<TokenStream2> {
+    let env = find_env_param(&func)?;
+    let caller = &args.caller;
+    let role = role_tokens(&args.role, &env)?;
+
+    let check = match &args.rbac {
+        // Cross-contract: invoke `has_role` on the deployed RbacContract. A failed call
+        // counts as a missing role.
+        Some(rbac) => quote! {
+            {
+                let __rbac_address: ::soroban_sdk::Address = #rbac;
+                ::core::matches!(
+                    #env.try_invoke_contract::<bool, ::soroban_sdk::Error>(
+                        &__rbac_address,
+                        &::soroban_sdk::Symbol::new(&#env, "has_role"),
+                        ::soroban_sdk::vec![
+                            &#env,
+                            ::soroban_sdk::IntoVal::into_val(&__rbac_role, &#env),
+                            ::soroban_sdk::IntoVal::into_val(&#caller, &#env),
+                        ],
+                    ),
+                    ::core::result::Result::Ok(::core::result::Result::Ok(true))
+                )
+            }
+        },
+        // Embedded: read RBAC state from this contract's own storage
+        None => quote! {
+            ::stellar_keystone_rbac::roles::has_role(&#env, &__rbac_role, &#caller)
+        },
+    };
+
+    let err = match &args.err {
+        Some(err) => quote! { #err },
+        // Embedded mode only; cross-contract mode requires `err`
+        None => quote! {
+            ::core::convert::From::from(::stellar_keystone_rbac::RbacError::NotAuthorized)
+        },
+    };
+
+    let guard: syn::Stmt = syn::parse2(quote! {
+        {
+            let __rbac_role: ::soroban_sdk::Symbol = #role;
+            #caller.require_auth();
+            if !#check {
+                return ::core::result::Result::Err(#err);
+            }
+        }
+    })?;
+    func.block.stmts.insert(0, guard);
+
+    Ok(quote! { #func })
+}
+
+/// Find the identifier of the function's `Env` parameter.
+fn find_env_param(func: &ItemFn) -> syn::Result<Ident> {
+    for input in &func.sig.inputs {
+        if let FnArg::Typed(pat_type) = input {
+            let is_env = match pat_type.ty.as_ref() {
+                Type::Path(path) => path
+                    .path
+                    .segments
+                    .last()
+                    .is_some_and(|segment| segment.ident == "Env"),
+                _ => false,
+            };
+            if let (true, Pat::Ident(pat)) = (is_env, pat_type.pat.as_ref()) {
+                return Ok(pat.ident.clone());
+            }
+        }
+    }
+
+    Err(syn::Error::new_spanned(
+        &func.sig,
+        "#[only_role] requires the function to take an `Env` parameter",
+    ))
+}
+
+/// Turn the role argument into an expression producing a `Symbol`.
+fn role_tokens(role: &Expr, env: &Ident) -> syn::Result<TokenStream2> {
+    match role {
+        Expr::Lit(ExprLit {
+            lit: Lit::Str(name),
+            ..
+        }) => {
+            let value = name.value();
+            let valid = value.len() <= MAX_SYMBOL_LEN
+                && value.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+            if !valid {
+                return Err(syn::Error::new(
+                    name.span(),
+                    "role name must be at most 32 characters of `a-zA-Z0-9_`",
+                ));
+            }
+            Ok(quote! { ::soroban_sdk::Symbol::new(&#env, #name) })
+        }
+        other => Ok(quote! { #other }),
+    }
+}
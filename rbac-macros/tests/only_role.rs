@@ -0,0 +1,199 @@
+#![cfg(test)]
+
+use soroban_sdk::testutils::Address as _;
+use soroban_sdk::{
+    contract, contracterror, contractimpl, symbol_short, Address, Env, IntoVal, Symbol,
+};
+use stellar_keystone_rbac::{roles, RbacContract, RbacContractClient, RbacError};
+use stellar_keystone_rbac_macros::only_role;
+
+const WITHDRAW_ROLE: Symbol = symbol_short!("WITHDRAW");
+
+// =============================================================================
+// Embedded mode: RBAC state lives in the guarded contract's own storage
+// =============================================================================
+
+#[contract]
+pub struct EmbeddedVault;
+
+#[contractimpl]
+impl EmbeddedVault {
+    pub fn __constructor(env: Env, admin: Address) {
+        roles::initialize(&env, &admin).unwrap();
+    }
+
+    pub fn create_role(env: Env, admin: Address) -> Result<(), RbacError> {
+        roles::create_role(
+            &env,
+            &admin,
+            &WITHDRAW_ROLE,
+            &stellar_keystone_rbac::DEFAULT_ADMIN_ROLE,
+        )
+    }
+
+    pub fn grant_role(env: Env, admin: Address, account: Address) -> Result<(), RbacError> {
+        roles::grant_role(&env, &admin, &WITHDRAW_ROLE, &account, 0)
+    }
+
+    #[only_role("WITHDRAW", caller)]
+    pub fn withdraw(env: Env, caller: Address, amount: i128) -> Result<i128, RbacError> {
+        Ok(amount)
+    }
+
+    // A bare identifier is the const's value, not a symbol named `WITHDRAW_ROLE`
+    #[only_role(WITHDRAW_ROLE, caller)]
+    pub fn withdraw_by_ident(env: Env, caller: Address) -> Result<(), RbacError> {
+        Ok(())
+    }
+
+    #[only_role(crate::WITHDRAW_ROLE, caller)]
+    pub fn withdraw_by_const(env: Env, caller: Address) -> Result<(), RbacError> {
+        Ok(())
+    }
+}
+
+// =============================================================================
+// Cross-contract mode: RBAC state lives in a deployed RbacContract
+// =============================================================================
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum VaultError {
+    NotAuthorized = 1,
+}
+
+#[contract]
+pub struct RemoteVault;
+
+#[contractimpl]
+impl RemoteVault {
+    pub fn __constructor(env: Env, rbac: Address) {
+        env.storage().instance().set(&symbol_short!("rbac"), &rbac);
+    }
+
+    #[only_role(
+        "WITHDRAW",
+        caller,
+        rbac = env.storage().instance().get(&symbol_short!("rbac")).unwrap(),
+        err = VaultError::NotAuthorized
+    )]
+    pub fn withdraw(env: Env, caller: Address, amount: i128) -> Result<i128, VaultError> {
+        Ok(amount)
+    }
+}
+
+fn setup_embedded() -> (Env, Address, EmbeddedVaultClient<'static>) {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let vault_id = env.register(EmbeddedVault, (admin.clone(),));
+    let vault = EmbeddedVaultClient::new(&env, &vault_id);
+    vault.create_role(&admin);
+
+    (env, admin, vault)
+}
+
+fn setup_remote() -> (
+    Env,
+    Address,
+    RbacContractClient<'static>,
+    RemoteVaultClient<'static>,
+) {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let rbac_id = env.register(RbacContract, ());
+    let rbac = RbacContractClient::new(&env, &rbac_id);
+    let admin = Address::generate(&env);
+    rbac.initialize(&admin);
+    rbac.create_role(&admin, &WITHDRAW_ROLE, &rbac.default_admin_role());
+
+    let vault_id = env.register(RemoteVault, (rbac_id,));
+    let vault = RemoteVaultClient::new(&env, &vault_id);
+
+    (env, admin, rbac, vault)
+}
+
+#[test]
+fn test_embedded_role_holder_passes() {
+    let (env, admin, vault) = setup_embedded();
+
+    let account = Address::generate(&env);
+    vault.grant_role(&admin, &account);
+
+    assert_eq!(vault.withdraw(&account, &100), 100);
+    vault.withdraw_by_ident(&account);
+    vault.withdraw_by_const(&account);
+}
+
+#[test]
+fn test_embedded_missing_role_returns_default_error() {
+    let (env, _admin, vault) = setup_embedded();
+
+    let outsider = Address::generate(&env);
+    let result = vault.try_withdraw(&outsider, &100);
+    assert_eq!(result.unwrap_err(), Ok(RbacError::NotAuthorized));
+    let result = vault.try_withdraw_by_ident(&outsider);
+    assert_eq!(result.unwrap_err(), Ok(RbacError::NotAuthorized));
+}
+
+#[test]
+fn test_remote_role_holder_passes() {
+    let (env, admin, rbac, vault) = setup_remote();
+
+    let account = Address::generate(&env);
+    rbac.grant_role(&admin, &WITHDRAW_ROLE, &account, &0);
+
+    assert_eq!(vault.withdraw(&account, &100), 100);
+}
+
+#[test]
+fn test_remote_missing_role_returns_custom_error() {
+    let (env, admin, rbac, vault) = setup_remote();
+
+    let account = Address::generate(&env);
+    rbac.grant_role(&admin, &WITHDRAW_ROLE, &account, &0);
+    rbac.revoke_role(&admin, &WITHDRAW_ROLE, &account);
+
+    let result = vault.try_withdraw(&account, &100);
+    assert_eq!(result.unwrap_err(), Ok(VaultError::NotAuthorized));
+}
+
+#[test]
+fn test_remote_call_failure_returns_custom_error() {
+    let (env, admin, _rbac, _vault) = setup_remote();
+    let account = Address::generate(&env);
+
+    // Neither an account address nor a contract without `has_role` can answer the check
+    let not_rbac = [
+        Address::generate(&env),
+        env.register(EmbeddedVault, (admin,)),
+    ];
+    for rbac in not_rbac {
+        let vault = RemoteVaultClient::new(&env, &env.register(RemoteVault, (rbac,)));
+        let result = vault.try_withdraw(&account, &100);
+        assert_eq!(result.unwrap_err(), Ok(VaultError::NotAuthorized));
+    }
+}
+
+#[test]
+fn test_guard_requires_caller_signature() {
+    let (env, admin, rbac, vault) = setup_remote();
+
+    let account = Address::generate(&env);
+    rbac.grant_role(&admin, &WITHDRAW_ROLE, &account, &0);
+
+    // Only the role admin signs; the role holder's signature is missing
+    env.mock_auths(&[soroban_sdk::testutils::MockAuth {
+        address: &admin,
+        invoke: &soroban_sdk::testutils::MockAuthInvoke {
+            contract: &vault.address,
+            fn_name: "withdraw",
+            args: (account.clone(), 100i128).into_val(&env),
+            sub_invokes: &[],
+        },
+    }]);
+    assert!(vault.try_withdraw(&account, &100).is_err());
+}
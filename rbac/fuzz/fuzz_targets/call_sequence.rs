@@ -0,0 +1,364 @@
+//! Arbitrary orderings of every `RbacContract` entry point, starting from an
+//! uninitialized contract.
+
+#![no_main]
+
+use arbitrary::Arbitrary;
+use libfuzzer_sys::fuzz_target;
+use stellar_keystone_rbac_fuzz::{check, check_infallible, FuzzSymbol, FuzzTime, Harness};
+
+/// Longest operation sequence run per input.
+const MAX_CALLS: usize = 64;
+
+#[derive(Arbitrary, Debug)]
+enum Op {
+    Initialize {
+        admin: u8,
+    },
+    CreateRole {
+        caller: u8,
+        role: FuzzSymbol,
+        admin_role: FuzzSymbol,
+    },
+    SetRoleAdmin {
+        caller: u8,
+        role: FuzzSymbol,
+        admin_role: FuzzSymbol,
+    },
+    SetRoleMaxDuration {
+        caller: u8,
+        role: FuzzSymbol,
+        max_duration: FuzzTime,
+    },
+    SetRoleMaxMembers {
+        caller: u8,
+        role: FuzzSymbol,
+        max_members: u32,
+    },
+    AddRoleConflict {
+        caller: u8,
+        role_a: FuzzSymbol,
+        role_b: FuzzSymbol,
+    },
+    RemoveRoleConflict {
+        caller: u8,
+        role_a: FuzzSymbol,
+        role_b: FuzzSymbol,
+    },
+    SetFunctionRole {
+        caller: u8,
+        target: u8,
+        fn_name: FuzzSymbol,
+        role: FuzzSymbol,
+    },
+    RemoveFunctionRole {
+        caller: u8,
+        target: u8,
+        fn_name: FuzzSymbol,
+    },
+    AddFederatedRole {
+        caller: u8,
+        role: FuzzSymbol,
+        source: u8,
+        source_role: FuzzSymbol,
+    },
+    RemoveFederatedRole {
+        caller: u8,
+        role: FuzzSymbol,
+        source: u8,
+        source_role: FuzzSymbol,
+    },
+    GrantRole {
+        caller: u8,
+        role: FuzzSymbol,
+        account: u8,
+        expiry: FuzzTime,
+    },
+    GrantRoleScheduled {
+        caller: u8,
+        role: FuzzSymbol,
+        account: u8,
+        valid_from: FuzzTime,
+        expiry: FuzzTime,
+    },
+    GrantRoleToContract {
+        caller: u8,
+        role: FuzzSymbol,
+        contract: u8,
+        expiry: FuzzTime,
+    },
+    ExtendRole {
+        caller: u8,
+        role: FuzzSymbol,
+        account: u8,
+        new_expiry: FuzzTime,
+    },
+    RevokeRole {
+        caller: u8,
+        role: FuzzSymbol,
+        account: u8,
+    },
+    RenounceRole {
+        account: u8,
+        role: FuzzSymbol,
+    },
+    DelegateRole {
+        delegator: u8,
+        role: FuzzSymbol,
+        delegate: u8,
+        expiry: FuzzTime,
+    },
+    RevokeDelegation {
+        delegator: u8,
+        role: FuzzSymbol,
+        delegate: u8,
+    },
+    CleanupExpiredRole {
+        role: FuzzSymbol,
+        account: u8,
+    },
+    CleanupExpiredDelegation {
+        role: FuzzSymbol,
+        delegate: u8,
+    },
+    RequireRole {
+        role: FuzzSymbol,
+        account: u8,
+    },
+    Authorize {
+        role: FuzzSymbol,
+        account: u8,
+    },
+    CheckCall {
+        target: u8,
+        fn_name: FuzzSymbol,
+        account: u8,
+    },
+    Read {
+        role: FuzzSymbol,
+        account: u8,
+    },
+    SetTime(FuzzTime),
+}
+
+fuzz_target!(|ops: Vec<Op>| {
+    let h = Harness::new();
+    let c = &h.client;
+
+    for op in ops.iter().take(MAX_CALLS) {
+        match op {
+            Op::Initialize { admin } => check(c.try_initialize(&h.address(*admin))),
+            Op::CreateRole {
+                caller,
+                role,
+                admin_role,
+            } => check(c.try_create_role(
+                &h.address(*caller),
+                &h.symbol(role),
+                &h.symbol(admin_role),
+            )),
+            Op::SetRoleAdmin {
+                caller,
+                role,
+                admin_role,
+            } => check(c.try_set_role_admin(
+                &h.address(*caller),
+                &h.symbol(role),
+                &h.symbol(admin_role),
+            )),
+            Op::SetRoleMaxDuration {
+                caller,
+                role,
+                max_duration,
+            } => check(c.try_set_role_max_duration(
+                &h.address(*caller),
+                &h.symbol(role),
+                &h.time(*max_duration),
+            )),
+            Op::SetRoleMaxMembers {
+                caller,
+                role,
+                max_members,
+            } => {
+                check(c.try_set_role_max_members(&h.address(*caller), &h.symbol(role), max_members))
+            }
+            Op::AddRoleConflict {
+                caller,
+                role_a,
+                role_b,
+            } => check(c.try_add_role_conflict(
+                &h.address(*caller),
+                &h.symbol(role_a),
+                &h.symbol(role_b),
+            )),
+            Op::RemoveRoleConflict {
+                caller,
+                role_a,
+                role_b,
+            } => check(c.try_remove_role_conflict(
+                &h.address(*caller),
+                &h.symbol(role_a),
+                &h.symbol(role_b),
+            )),
+            Op::SetFunctionRole {
+                caller,
+                target,
+                fn_name,
+                role,
+            } => check(c.try_set_function_role(
+                &h.address(*caller),
+                &h.address(*target),
+                &h.symbol(fn_name),
+                &h.symbol(role),
+            )),
+            Op::RemoveFunctionRole {
+                caller,
+                target,
+                fn_name,
+            } => check(c.try_remove_function_role(
+                &h.address(*caller),
+                &h.address(*target),
+                &h.symbol(fn_name),
+            )),
+            Op::AddFederatedRole {
+                caller,
+                role,
+                source,
+                source_role,
+            } => check(c.try_add_federated_role(
+                &h.address(*caller),
+                &h.symbol(role),
+                &h.address(*source),
+                &h.symbol(source_role),
+            )),
+            Op::RemoveFederatedRole {
+                caller,
+                role,
+                source,
+                source_role,
+            } => check(c.try_remove_federated_role(
+                &h.address(*caller),
+                &h.symbol(role),
+                &h.address(*source),
+                &h.symbol(source_role),
+            )),
+            Op::GrantRole {
+                caller,
+                role,
+                account,
+                expiry,
+            } => check(c.try_grant_role(
+                &h.address(*caller),
+                &h.symbol(role),
+                &h.address(*account),
+                &h.time(*expiry),
+            )),
+            Op::GrantRoleScheduled {
+                caller,
+                role,
+                account,
+                valid_from,
+                expiry,
+            } => check(c.try_grant_role_scheduled(
+                &h.address(*caller),
+                &h.symbol(role),
+                &h.address(*account),
+                &h.time(*valid_from),
+                &h.time(*expiry),
+            )),
+            Op::GrantRoleToContract {
+                caller,
+                role,
+                contract,
+                expiry,
+            } => check(c.try_grant_role_to_contract(
+                &h.address(*caller),
+                &h.symbol(role),
+                &h.address(*contract),
+                &h.time(*expiry),
+            )),
+            Op::ExtendRole {
+                caller,
+                role,
+                account,
+                new_expiry,
+            } => check(c.try_extend_role(
+                &h.address(*caller),
+                &h.symbol(role),
+                &h.address(*account),
+                &h.time(*new_expiry),
+            )),
+            Op::RevokeRole {
+                caller,
+                role,
+                account,
+            } => {
+                check(c.try_revoke_role(&h.address(*caller), &h.symbol(role), &h.address(*account)))
+            }
+            Op::RenounceRole { account, role } => {
+                check(c.try_renounce_role(&h.address(*account), &h.symbol(role)))
+            }
+            Op::DelegateRole {
+                delegator,
+                role,
+                delegate,
+                expiry,
+            } => check(c.try_delegate_role(
+                &h.address(*delegator),
+                &h.symbol(role),
+                &h.address(*delegate),
+                &h.time(*expiry),
+            )),
+            Op::RevokeDelegation {
+                delegator,
+                role,
+                delegate,
+            } => check(c.try_revoke_delegation(
+                &h.address(*delegator),
+                &h.symbol(role),
+                &h.address(*delegate),
+            )),
+            Op::CleanupExpiredRole { role, account } => {
+                check_infallible(c.try_cleanup_expired_role(&h.symbol(role), &h.address(*account)))
+            }
+            Op::CleanupExpiredDelegation { role, delegate } => check_infallible(
+                c.try_cleanup_expired_delegation(&h.symbol(role), &h.address(*delegate)),
+            ),
+            Op::RequireRole { role, account } => {
+                check(c.try_require_role(&h.symbol(role), &h.address(*account)))
+            }
+            Op::Authorize { role, account } => {
+                check(c.try_authorize(&h.symbol(role), &h.address(*account)))
+            }
+            Op::CheckCall {
+                target,
+                fn_name,
+                account,
+            } => check(c.try_check_call(
+                &h.address(*target),
+                &h.symbol(fn_name),
+                &h.address(*account),
+            )),
+            Op::Read { role, account } => {
+                let role = h.symbol(role);
+                let account = h.address(*account);
+                check_infallible(c.try_has_role(&role, &account));
+                check_infallible(c.try_has_local_role(&role, &account));
+                check_infallible(c.try_get_role_expiry(&role, &account));
+                check_infallible(c.try_get_role_grant(&role, &account));
+                check_infallible(c.try_get_role_member_count(&role));
+                check_infallible(c.try_get_role_conflicts(&role));
+                check_infallible(c.try_get_conflicting_roles(&role, &account));
+                check_infallible(c.try_get_delegation(&role, &account));
+                check_infallible(c.try_get_federated_roles(&role));
+                check_infallible(c.try_get_function_role(&account, &role));
+                check_infallible(c.try_get_role_admin(&role));
+                check_infallible(c.try_get_role_policy(&role));
+                check_infallible(c.try_role_exists(&role));
+                check_infallible(c.try_get_deployer());
+                check_infallible(c.try_get_revocation_epoch());
+            }
+            Op::SetTime(time) => h.set_time(*time),
+        }
+    }
+});
@@ -0,0 +1,96 @@
+//! Grant windows, extensions, delegations and duration caps with extreme timestamps,
+//! against an initialized contract where account 0 is the admin.
+
+#![no_main]
+
+use arbitrary::Arbitrary;
+use libfuzzer_sys::fuzz_target;
+use soroban_sdk::symbol_short;
+use stellar_keystone_rbac_fuzz::{check, check_infallible, FuzzTime, Harness};
+
+/// Longest operation sequence run per input.
+const MAX_CALLS: usize = 64;
+
+#[derive(Arbitrary, Debug)]
+enum Op {
+    SetMaxDuration(FuzzTime),
+    Grant {
+        account: u8,
+        valid_from: FuzzTime,
+        expiry: FuzzTime,
+    },
+    Extend {
+        account: u8,
+        new_expiry: FuzzTime,
+    },
+    Delegate {
+        delegator: u8,
+        delegate: u8,
+        expiry: FuzzTime,
+    },
+    Cleanup {
+        account: u8,
+    },
+    Check {
+        account: u8,
+    },
+    SetTime(FuzzTime),
+}
+
+fuzz_target!(|input: (FuzzTime, Vec<Op>)| {
+    let (start, ops) = input;
+    let h = Harness::new();
+    let c = &h.client;
+    let admin = h.address(0);
+    let role = symbol_short!("MINTER");
+
+    h.set_time(start);
+    c.initialize(&admin);
+    c.create_role(&admin, &role, &c.default_admin_role());
+
+    for op in ops.iter().take(MAX_CALLS) {
+        match op {
+            Op::SetMaxDuration(max_duration) => {
+                check(c.try_set_role_max_duration(&admin, &role, &h.time(*max_duration)))
+            }
+            Op::Grant {
+                account,
+                valid_from,
+                expiry,
+            } => check(c.try_grant_role_scheduled(
+                &admin,
+                &role,
+                &h.address(*account),
+                &h.time(*valid_from),
+                &h.time(*expiry),
+            )),
+            Op::Extend {
+                account,
+                new_expiry,
+            } => {
+                check(c.try_extend_role(&admin, &role, &h.address(*account), &h.time(*new_expiry)))
+            }
+            Op::Delegate {
+                delegator,
+                delegate,
+                expiry,
+            } => check(c.try_delegate_role(
+                &h.address(*delegator),
+                &role,
+                &h.address(*delegate),
+                &h.time(*expiry),
+            )),
+            Op::Cleanup { account } => {
+                check_infallible(c.try_cleanup_expired_role(&role, &h.address(*account)))
+            }
+            Op::Check { account } => {
+                let account = h.address(*account);
+                check_infallible(c.try_has_role(&role, &account));
+                check_infallible(c.try_get_role_grant(&role, &account));
+                check_infallible(c.try_get_delegation(&role, &account));
+                check(c.try_authorize(&role, &account));
+            }
+            Op::SetTime(time) => h.set_time(*time),
+        }
+    }
+});
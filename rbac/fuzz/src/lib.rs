@@ -0,0 +1,158 @@
+//! Shared harness for the RBAC fuzz targets.
+//!
+//! Targets drive `RbacContractClient` through its `try_*` methods and pass every
+//! result to [`check`], which fails the run on anything other than success or an
+//! `RbacError` code, and on `StorageCorrupted`, which a fresh contract must never reach.
+
+use arbitrary::Arbitrary;
+use soroban_sdk::testutils::{Address as _, EnvTestConfig, Ledger};
+use soroban_sdk::{symbol_short, Address, Env, InvokeError, Symbol};
+use stellar_keystone_rbac::{RbacContract, RbacContractClient, RbacError};
+
+/// Roles most inputs pick from, so calls hit existing state; index 0 is DEFAULT_ADMIN_ROLE.
+const KNOWN_ROLES: [Symbol; 4] = [
+    symbol_short!("DEF_ADMIN"),
+    symbol_short!("MINTER"),
+    symbol_short!("BURNER"),
+    symbol_short!("PAUSER"),
+];
+
+/// Number of plain accounts in the address pool.
+const ACCOUNTS: usize = 4;
+
+/// Characters allowed in a `Symbol`.
+const SYMBOL_CHARS: &[u8] = b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789_";
+
+/// Longest `Symbol` the host accepts.
+const MAX_SYMBOL_LEN: usize = 32;
+
+/// A `Symbol` argument: usually a known role, sometimes arbitrary.
+#[derive(Arbitrary, Debug)]
+pub enum FuzzSymbol {
+    Known(u8),
+    /// Bytes mapped onto the symbol alphabet, truncated to 32 characters
+    Raw(Vec<u8>),
+}
+
+impl FuzzSymbol {
+    pub fn to_symbol(&self, env: &Env) -> Symbol {
+        match self {
+            FuzzSymbol::Known(i) => KNOWN_ROLES[*i as usize % KNOWN_ROLES.len()].clone(),
+            FuzzSymbol::Raw(bytes) => {
+                let chars: Vec<u8> = bytes
+                    .iter()
+                    .take(MAX_SYMBOL_LEN)
+                    .map(|b| SYMBOL_CHARS[*b as usize % SYMBOL_CHARS.len()])
+                    .collect();
+                Symbol::new(env, core::str::from_utf8(&chars).unwrap())
+            }
+        }
+    }
+}
+
+/// A timestamp argument, relative to the ledger clock or absolute, with the
+/// extremes spelled out so they are hit often.
+#[derive(Arbitrary, Debug, Clone, Copy)]
+pub enum FuzzTime {
+    Zero,
+    Max,
+    Now,
+    Before(u32),
+    After(u32),
+    Raw(u64),
+}
+
+impl FuzzTime {
+    pub fn resolve(self, env: &Env) -> u64 {
+        let now = env.ledger().timestamp();
+        match self {
+            FuzzTime::Zero => 0,
+            FuzzTime::Max => u64::MAX,
+            FuzzTime::Now => now,
+            FuzzTime::Before(delta) => now.saturating_sub(delta as u64),
+            FuzzTime::After(delta) => now.saturating_add(delta as u64),
+            FuzzTime::Raw(value) => value,
+        }
+    }
+}
+
+/// A fresh `RbacContract` and a pool of addresses to call it with.
+pub struct Harness {
+    pub env: Env,
+    pub client: RbacContractClient<'static>,
+    /// Plain accounts, the contract itself and a second, initialized `RbacContract`
+    /// usable as a federated source
+    pub addresses: Vec<Address>,
+}
+
+impl Harness {
+    /// Register an uninitialized contract. All auths are mocked: the targets exercise
+    /// the contract's own checks, not signature verification.
+    pub fn new() -> Self {
+        let env = Env::new_with_config(EnvTestConfig {
+            capture_snapshot_at_drop: false,
+        });
+        env.mock_all_auths();
+
+        let client = RbacContractClient::new(&env, &env.register(RbacContract, ()));
+        let mut addresses: Vec<Address> = (0..ACCOUNTS).map(|_| Address::generate(&env)).collect();
+        addresses.push(client.address.clone());
+
+        let peer = RbacContractClient::new(&env, &env.register(RbacContract, ()));
+        peer.initialize(&addresses[0]);
+        addresses.push(peer.address.clone());
+
+        Harness {
+            env,
+            client,
+            addresses,
+        }
+    }
+
+    /// Pick an address from the pool.
+    pub fn address(&self, index: u8) -> Address {
+        self.addresses[index as usize % self.addresses.len()].clone()
+    }
+
+    pub fn symbol(&self, symbol: &FuzzSymbol) -> Symbol {
+        symbol.to_symbol(&self.env)
+    }
+
+    pub fn time(&self, time: FuzzTime) -> u64 {
+        time.resolve(&self.env)
+    }
+
+    pub fn set_time(&self, time: FuzzTime) {
+        let timestamp = self.time(time);
+        self.env.ledger().with_mut(|li| li.timestamp = timestamp);
+    }
+}
+
+impl Default for Harness {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Fail unless an entry point returning `Result<_, RbacError>` succeeded or returned
+/// an `RbacError` other than `StorageCorrupted`.
+pub fn check<T, C: core::fmt::Debug>(result: Result<Result<T, C>, Result<RbacError, InvokeError>>) {
+    match result {
+        Ok(Ok(_)) => {}
+        Ok(Err(err)) => panic!("return value did not convert: {err:?}"),
+        Err(Ok(RbacError::StorageCorrupted)) => panic!("reached StorageCorrupted"),
+        Err(Ok(_)) => {}
+        Err(Err(err)) => panic!("failed without an RbacError: {err:?}"),
+    }
+}
+
+/// Fail unless an entry point that does not return `Result` succeeded.
+pub fn check_infallible<T, C: core::fmt::Debug, E: core::fmt::Debug>(
+    result: Result<Result<T, C>, Result<E, InvokeError>>,
+) {
+    match result {
+        Ok(Ok(_)) => {}
+        Ok(Err(err)) => panic!("return value did not convert: {err:?}"),
+        Err(err) => panic!("getter failed: {err:?}"),
+    }
+}
@@ -22,4 +22,30 @@ pub enum RbacError {
     AlreadyInitialized = 7,
     /// internal storage state is corrupted
     StorageCorrupted = 8,
+    /// grant is open-ended or longer than the role's max duration
+    ExceedsMaxDuration = 9,
+    /// account holds a role that is mutually exclusive with the one being granted
+    ConflictingRole = 10,
+    /// a role cannot conflict with itself
+    InvalidRoleConflict = 11,
+    /// role already has its maximum number of members
+    MaxMembersReached = 12,
+    /// no role is mapped to the called function
+    FunctionNotMapped = 13,
+    /// an account cannot delegate a role to itself
+    InvalidDelegation = 14,
+    /// no delegation of the role from this delegator
+    DelegationNotFound = 15,
+    /// a role cannot federate to this contract itself
+    InvalidFederation = 16,
+    /// role already has the maximum number of federated sources
+    TooManyFederatedSources = 17,
+    /// address is not a deployed Wasm contract
+    NotAContract = 18,
+    /// the two roles have no conflict configured
+    RoleConflictNotFound = 19,
+    /// delegator already has the maximum number of delegates for the role
+    TooManyDelegates = 20,
+    /// the source is not federated for the role
+    FederationNotFound = 21,
 }
@@ -1,6 +1,9 @@
 //! Event types
 //! //! Events are emitted using Soroban's contractevent system for off-chain indexing.
 //! Note: Using deprecated `publish` method until full migration to `#[contractevent]`.
+#![allow(deprecated)]
+
+use crate::storage::RolePolicy;
 use soroban_sdk::{contracttype, Address, Env, Symbol};
 
 /// Event emitted when a new role is created.
@@ -30,6 +33,28 @@ pub struct RoleGrantedEvent {
     pub granted_by: Address,
 }
 
+/// Event emitted when a future-dated role grant is scheduled.
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct RoleScheduledEvent {
+    pub role: Symbol,
+    pub account: Address,
+    pub valid_from: u64,
+    pub expiry: u64,
+    pub granted_by: Address,
+}
+
+/// Event emitted when an existing role grant is extended.
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct RoleExtendedEvent {
+    pub role: Symbol,
+    pub account: Address,
+    pub old_expiry: u64,
+    pub new_expiry: u64,
+    pub extended_by: Address,
+}
+
 /// Event emitted when a role is revoked from an account.
 #[contracttype]
 #[derive(Clone, Debug)]
@@ -48,6 +73,84 @@ pub struct RoleExpiredEvent {
     pub expired_at: u64,
 }
 
+/// Event emitted when a role's grant policy is changed.
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct RolePolicyChangedEvent {
+    pub role: Symbol,
+    pub policy: RolePolicy,
+}
+
+/// Event emitted when two roles are made mutually exclusive.
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct RoleConflictAddedEvent {
+    pub role_a: Symbol,
+    pub role_b: Symbol,
+}
+
+/// Event emitted when a mutual-exclusion constraint is removed.
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct RoleConflictRemovedEvent {
+    pub role_a: Symbol,
+    pub role_b: Symbol,
+}
+
+/// Event emitted when a role is mapped to a contract function.
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct FunctionRoleSetEvent {
+    pub target: Address,
+    pub fn_name: Symbol,
+    pub role: Symbol,
+}
+
+/// Event emitted when a contract function's role mapping is removed.
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct FunctionRoleRemovedEvent {
+    pub target: Address,
+    pub fn_name: Symbol,
+}
+
+/// Event emitted when a role holder delegates the role.
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct RoleDelegatedEvent {
+    pub role: Symbol,
+    pub delegator: Address,
+    pub delegate: Address,
+    pub expiry: u64,
+}
+
+/// Event emitted when a delegation is revoked, including when its delegator loses the role.
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct DelegationRevokedEvent {
+    pub role: Symbol,
+    pub delegator: Address,
+    pub delegate: Address,
+}
+
+/// Event emitted when a role in an external RBAC contract is trusted to satisfy a local role.
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct FederatedRoleAddedEvent {
+    pub role: Symbol,
+    pub source_rbac: Address,
+    pub source_role: Symbol,
+}
+
+/// Event emitted when a federated source is removed from a local role.
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct FederatedRoleRemovedEvent {
+    pub role: Symbol,
+    pub source_rbac: Address,
+    pub source_role: Symbol,
+}
+
 /// Emit a RoleCreated event.
 pub fn role_created(env: &Env, role: Symbol, admin_role: Symbol) {
     env.events().publish(
@@ -76,6 +179,44 @@ pub fn role_granted(env: &Env, role: Symbol, account: Address, expiry: u64, gran
     );
 }
 
+/// Emit a RoleScheduled event.
+pub fn role_scheduled(
+    env: &Env,
+    role: Symbol,
+    account: Address,
+    valid_from: u64,
+    expiry: u64,
+    granted_by: Address,
+) {
+    env.events().publish(
+        (
+            soroban_sdk::symbol_short!("RoleSched"),
+            role.clone(),
+            account.clone(),
+        ),
+        (valid_from, expiry, granted_by),
+    );
+}
+
+/// Emit a RoleExtended event.
+pub fn role_extended(
+    env: &Env,
+    role: Symbol,
+    account: Address,
+    old_expiry: u64,
+    new_expiry: u64,
+    extended_by: Address,
+) {
+    env.events().publish(
+        (
+            soroban_sdk::symbol_short!("RoleExtnd"),
+            role.clone(),
+            account.clone(),
+        ),
+        (old_expiry, new_expiry, extended_by),
+    );
+}
+
 /// Emit a RoleRevoked event.
 pub fn role_revoked(env: &Env, role: Symbol, account: Address, revoked_by: Address) {
     env.events().publish(
@@ -99,3 +240,91 @@ pub fn role_expired(env: &Env, role: Symbol, account: Address, expired_at: u64)
         expired_at,
     );
 }
+
+/// Emit a RolePolicyChanged event.
+pub fn role_policy_changed(env: &Env, role: Symbol, policy: RolePolicy) {
+    env.events().publish(
+        (soroban_sdk::symbol_short!("PolicyChg"), role.clone()),
+        policy,
+    );
+}
+
+/// Emit a RoleConflictAdded event.
+pub fn role_conflict_added(env: &Env, role_a: Symbol, role_b: Symbol) {
+    env.events().publish(
+        (soroban_sdk::symbol_short!("ConflAdd"), role_a.clone()),
+        role_b,
+    );
+}
+
+/// Emit a RoleConflictRemoved event.
+pub fn role_conflict_removed(env: &Env, role_a: Symbol, role_b: Symbol) {
+    env.events().publish(
+        (soroban_sdk::symbol_short!("ConflRem"), role_a.clone()),
+        role_b,
+    );
+}
+
+/// Emit a FunctionRoleSet event.
+pub fn function_role_set(env: &Env, target: Address, fn_name: Symbol, role: Symbol) {
+    env.events().publish(
+        (
+            soroban_sdk::symbol_short!("FnRoleSet"),
+            target.clone(),
+            fn_name.clone(),
+        ),
+        role,
+    );
+}
+
+/// Emit a FunctionRoleRemoved event.
+pub fn function_role_removed(env: &Env, target: Address, fn_name: Symbol) {
+    env.events().publish(
+        (
+            soroban_sdk::symbol_short!("FnRoleRem"),
+            target.clone(),
+            fn_name.clone(),
+        ),
+        (),
+    );
+}
+
+/// Emit a RoleDelegated event.
+pub fn role_delegated(env: &Env, role: Symbol, delegator: Address, delegate: Address, expiry: u64) {
+    env.events().publish(
+        (
+            soroban_sdk::symbol_short!("RoleDeleg"),
+            role.clone(),
+            delegate.clone(),
+        ),
+        (delegator, expiry),
+    );
+}
+
+/// Emit a DelegationRevoked event.
+pub fn delegation_revoked(env: &Env, role: Symbol, delegator: Address, delegate: Address) {
+    env.events().publish(
+        (
+            soroban_sdk::symbol_short!("DelegRevk"),
+            role.clone(),
+            delegate.clone(),
+        ),
+        delegator,
+    );
+}
+
+/// Emit a FederatedRoleAdded event.
+pub fn federated_role_added(env: &Env, role: Symbol, source_rbac: Address, source_role: Symbol) {
+    env.events().publish(
+        (soroban_sdk::symbol_short!("FedAdd"), role.clone()),
+        (source_rbac, source_role),
+    );
+}
+
+/// Emit a FederatedRoleRemoved event.
+pub fn federated_role_removed(env: &Env, role: Symbol, source_rbac: Address, source_role: Symbol) {
+    env.events().publish(
+        (soroban_sdk::symbol_short!("FedRem"), role.clone()),
+        (source_rbac, source_role),
+    );
+}
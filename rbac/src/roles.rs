@@ -0,0 +1,1560 @@
+//! Embeddable RBAC logic
+//!
+//! Every `RbacContract` entry point is a thin wrapper over a function in this module.
+//! Contracts that want RBAC in their own storage (instead of calling a deployed
+//! `RbacContract`) can depend on this crate with the `library` feature and call these
+//! functions directly from their own `#[contractimpl]`.
+//!
+//! All state lives in the calling contract's persistent storage under [`DataKey`].
+
+use crate::errors::RbacError;
+use crate::events;
+use crate::storage::{DataKey, Delegation, FederatedRole, RoleGrant, RolePolicy};
+use crate::DEFAULT_ADMIN_ROLE;
+use soroban_sdk::{panic_with_error, vec, Address, Env, Executable, IntoVal, Symbol, Vec};
+
+/// Maximum number of external RBAC sources a role can federate to. Bounds the
+/// cross-contract calls a single `has_role` check can make.
+pub const MAX_FEDERATED_SOURCES: u32 = 4;
+
+/// Maximum number of accounts a holder can delegate a role to. Bounds the delegations
+/// removed when the holder loses the role.
+pub const MAX_DELEGATES: u32 = 8;
+
+/// Initialize RBAC state, making `admin` the first holder of DEFAULT_ADMIN_ROLE.
+///
+/// # Errors
+/// Returns `AlreadyInitialized` if RBAC state is already initialized.
+///
+/// # Note
+/// Embedding contracts should call this once, typically from their constructor.
+pub fn initialize(env: &Env, admin: &Address) -> Result<(), RbacError> {
+    // Ensure not already initialized (use persistent storage)
+    if env.storage().persistent().has(&DataKey::Initialized) {
+        return Err(RbacError::AlreadyInitialized);
+    }
+
+    // Set initialized FIRST (atomicity: any failure after this is visible)
+    env.storage().persistent().set(&DataKey::Initialized, &true);
+
+    // Store deployer in persistent storage
+    env.storage().persistent().set(&DataKey::Deployer, admin);
+
+    // Setup DEFAULT_ADMIN_ROLE
+    let role = DEFAULT_ADMIN_ROLE;
+
+    // Mark role as existing (structural invariant)
+    env.storage()
+        .persistent()
+        .set(&DataKey::RoleExists(role.clone()), &true);
+
+    // Set role admin (self-admin for DEFAULT_ADMIN_ROLE)
+    env.storage()
+        .persistent()
+        .set(&DataKey::RoleAdmin(role.clone()), &role);
+
+    // Grant membership to admin
+    env.storage()
+        .persistent()
+        .set(&DataKey::RoleMember(role.clone(), admin.clone()), &true);
+    env.storage()
+        .persistent()
+        .set(&DataKey::RoleExpiry(role.clone(), admin.clone()), &0u64);
+    env.storage()
+        .persistent()
+        .set(&DataKey::RoleMemberCount(role.clone()), &1u32);
+
+    // Emit events
+    events::role_created(env, role.clone(), role.clone());
+    events::role_granted(env, role, admin.clone(), 0, admin.clone());
+
+    Ok(())
+}
+
+// =============================================================================
+// Role Management
+// =============================================================================
+
+/// Create a new role with a specified admin role.
+///
+/// # Authorization
+/// Caller must have DEFAULT_ADMIN_ROLE to create new roles.
+///
+/// # Errors
+/// - `RoleAlreadyExists` if the role already exists
+/// - `NotAuthorized` if caller lacks DEFAULT_ADMIN_ROLE
+/// - `RoleNotFound` if admin_role does not exist
+/// - `InvalidSelfAdmin` if role == admin_role (except DEFAULT_ADMIN_ROLE)
+///
+/// # Note
+/// Roles are immutable once created. There is no `delete_role` function.
+pub fn create_role(
+    env: &Env,
+    caller: &Address,
+    role: &Symbol,
+    admin_role: &Symbol,
+) -> Result<(), RbacError> {
+    // Authorize caller
+    require_admin(env, &DEFAULT_ADMIN_ROLE, caller)?;
+
+    // Reject if role already exists (strict create-only semantics)
+    if env
+        .storage()
+        .persistent()
+        .has(&DataKey::RoleExists(role.clone()))
+    {
+        return Err(RbacError::RoleAlreadyExists);
+    }
+
+    // Corruption check: RoleAdmin should not exist without RoleExists
+    if env
+        .storage()
+        .persistent()
+        .has(&DataKey::RoleAdmin(role.clone()))
+    {
+        // This should never happen — indicates corrupted state
+        panic_with_error!(env, RbacError::StorageCorrupted);
+    }
+
+    // Validate admin_role exists
+    require_role_exists(env, admin_role)?;
+
+    // Disallow self-admin unless it's DEFAULT_ADMIN_ROLE
+    if role == admin_role && *role != DEFAULT_ADMIN_ROLE {
+        return Err(RbacError::InvalidSelfAdmin);
+    }
+
+    // Mark role as existing
+    env.storage()
+        .persistent()
+        .set(&DataKey::RoleExists(role.clone()), &true);
+
+    // Store role admin mapping
+    env.storage()
+        .persistent()
+        .set(&DataKey::RoleAdmin(role.clone()), admin_role);
+
+    // Emit event
+    events::role_created(env, role.clone(), admin_role.clone());
+    Ok(())
+}
+
+/// Change the admin role for an existing role.
+///
+/// # Authorization
+/// Only callable by account with DEFAULT_ADMIN_ROLE.
+///
+/// # Errors
+/// - `RoleNotFound` if role or admin_role does not exist
+/// - `InvalidSelfAdmin` if role == admin_role (except DEFAULT_ADMIN_ROLE)
+pub fn set_role_admin(
+    env: &Env,
+    caller: &Address,
+    role: &Symbol,
+    admin_role: &Symbol,
+) -> Result<(), RbacError> {
+    // Only DEFAULT_ADMIN_ROLE can change role admins
+    require_admin(env, &DEFAULT_ADMIN_ROLE, caller)?;
+
+    // Validate role exists
+    require_role_exists(env, role)?;
+
+    // Validate admin_role exists
+    require_role_exists(env, admin_role)?;
+
+    // Disallow self-admin unless it's DEFAULT_ADMIN_ROLE
+    if role == admin_role && *role != DEFAULT_ADMIN_ROLE {
+        return Err(RbacError::InvalidSelfAdmin);
+    }
+
+    // Get previous admin for event
+    let previous_admin: Symbol = env
+        .storage()
+        .persistent()
+        .get(&DataKey::RoleAdmin(role.clone()))
+        .unwrap_or(DEFAULT_ADMIN_ROLE);
+
+    // Update admin role
+    env.storage()
+        .persistent()
+        .set(&DataKey::RoleAdmin(role.clone()), admin_role);
+
+    // Emit event
+    events::role_admin_changed(env, role.clone(), previous_admin, admin_role.clone());
+    Ok(())
+}
+
+/// Set the maximum grant duration for a role.
+///
+/// # Authorization
+/// Only callable by account with DEFAULT_ADMIN_ROLE.
+///
+/// # Errors
+/// - `RoleNotFound` if role does not exist
+///
+/// # Note
+/// Existing grants are not affected; the policy applies to future `grant_role` calls.
+pub fn set_role_max_duration(
+    env: &Env,
+    caller: &Address,
+    role: &Symbol,
+    max_duration: u64,
+) -> Result<(), RbacError> {
+    // Only DEFAULT_ADMIN_ROLE can change role policies
+    require_admin(env, &DEFAULT_ADMIN_ROLE, caller)?;
+
+    // Validate role exists
+    require_role_exists(env, role)?;
+
+    // Update policy
+    let mut policy = get_role_policy(env, role);
+    policy.max_duration = max_duration;
+    env.storage()
+        .persistent()
+        .set(&DataKey::RolePolicy(role.clone()), &policy);
+
+    // Emit event
+    events::role_policy_changed(env, role.clone(), policy);
+    Ok(())
+}
+
+/// Set the maximum number of accounts that can hold a role.
+///
+/// # Authorization
+/// Only callable by account with DEFAULT_ADMIN_ROLE.
+///
+/// # Errors
+/// - `RoleNotFound` if role does not exist
+///
+/// # Note
+/// Delegations count toward the cap. Lowering the cap below the current count does
+/// not evict members; it only blocks new grants and delegations until enough members
+/// are revoked, renounced or cleaned up.
+pub fn set_role_max_members(
+    env: &Env,
+    caller: &Address,
+    role: &Symbol,
+    max_members: u32,
+) -> Result<(), RbacError> {
+    // Only DEFAULT_ADMIN_ROLE can change role policies
+    require_admin(env, &DEFAULT_ADMIN_ROLE, caller)?;
+
+    // Validate role exists
+    require_role_exists(env, role)?;
+
+    // Update policy
+    let mut policy = get_role_policy(env, role);
+    policy.max_members = max_members;
+    env.storage()
+        .persistent()
+        .set(&DataKey::RolePolicy(role.clone()), &policy);
+
+    // Emit event
+    events::role_policy_changed(env, role.clone(), policy);
+    Ok(())
+}
+
+/// Mark two roles as mutually exclusive (separation of duties).
+///
+/// # Authorization
+/// Only callable by account with DEFAULT_ADMIN_ROLE.
+///
+/// # Errors
+/// - `RoleNotFound` if either role does not exist
+/// - `InvalidRoleConflict` if role_a == role_b
+///
+/// # Note
+/// Existing grants are not revoked. Use `get_conflicting_roles` to audit them.
+pub fn add_role_conflict(
+    env: &Env,
+    caller: &Address,
+    role_a: &Symbol,
+    role_b: &Symbol,
+) -> Result<(), RbacError> {
+    // Only DEFAULT_ADMIN_ROLE can manage conflicts
+    require_admin(env, &DEFAULT_ADMIN_ROLE, caller)?;
+
+    // Validate both roles exist
+    require_role_exists(env, role_a)?;
+    require_role_exists(env, role_b)?;
+
+    // A role cannot conflict with itself
+    if role_a == role_b {
+        return Err(RbacError::InvalidRoleConflict);
+    }
+
+    // Record the conflict symmetrically
+    insert_conflict(env, role_a, role_b);
+    insert_conflict(env, role_b, role_a);
+
+    // Emit event
+    events::role_conflict_added(env, role_a.clone(), role_b.clone());
+    Ok(())
+}
+
+/// Remove a mutual-exclusion constraint between two roles.
+///
+/// # Authorization
+/// Only callable by account with DEFAULT_ADMIN_ROLE.
+///
+/// # Errors
+/// - `RoleConflictNotFound` if the roles have no conflict configured
+pub fn remove_role_conflict(
+    env: &Env,
+    caller: &Address,
+    role_a: &Symbol,
+    role_b: &Symbol,
+) -> Result<(), RbacError> {
+    // Only DEFAULT_ADMIN_ROLE can manage conflicts
+    require_admin(env, &DEFAULT_ADMIN_ROLE, caller)?;
+
+    // Conflicts are stored symmetrically, so one side is enough to check
+    if !get_role_conflicts(env, role_a).contains(role_b) {
+        return Err(RbacError::RoleConflictNotFound);
+    }
+
+    // Remove the conflict from both sides
+    delete_conflict(env, role_a, role_b);
+    delete_conflict(env, role_b, role_a);
+
+    // Emit event
+    events::role_conflict_removed(env, role_a.clone(), role_b.clone());
+    Ok(())
+}
+
+// =============================================================================
+// Function Policies
+// =============================================================================
+
+/// Require a role for calls to `target.fn_name`, checked with `check_call`.
+///
+/// # Authorization
+/// Only callable by account with DEFAULT_ADMIN_ROLE.
+///
+/// # Errors
+/// - `RoleNotFound` if role does not exist
+///
+/// # Note
+/// Replaces any existing mapping, so a function can be re-mapped without redeploying
+/// the target contract. Re-mapping to a different role bumps the revocation epoch.
+pub fn set_function_role(
+    env: &Env,
+    caller: &Address,
+    target: &Address,
+    fn_name: &Symbol,
+    role: &Symbol,
+) -> Result<(), RbacError> {
+    // Only DEFAULT_ADMIN_ROLE can manage function policies
+    require_admin(env, &DEFAULT_ADMIN_ROLE, caller)?;
+
+    // Validate role exists
+    require_role_exists(env, role)?;
+
+    // Holders of the previous role lose access to the function
+    let key = DataKey::FunctionRole(target.clone(), fn_name.clone());
+    let previous: Option<Symbol> = env.storage().persistent().get(&key);
+    env.storage().persistent().set(&key, role);
+    if previous.is_some_and(|previous| previous != *role) {
+        bump_revocation_epoch(env);
+    }
+
+    // Emit event
+    events::function_role_set(env, target.clone(), fn_name.clone(), role.clone());
+    Ok(())
+}
+
+/// Remove the role mapping for `target.fn_name`.
+///
+/// # Authorization
+/// Only callable by account with DEFAULT_ADMIN_ROLE.
+///
+/// # Errors
+/// - `FunctionNotMapped` if no role is mapped to the function
+///
+/// # Note
+/// `check_call` rejects unmapped functions, so this blocks all calls to the function
+/// until it is mapped again.
+pub fn remove_function_role(
+    env: &Env,
+    caller: &Address,
+    target: &Address,
+    fn_name: &Symbol,
+) -> Result<(), RbacError> {
+    // Only DEFAULT_ADMIN_ROLE can manage function policies
+    require_admin(env, &DEFAULT_ADMIN_ROLE, caller)?;
+
+    let key = DataKey::FunctionRole(target.clone(), fn_name.clone());
+    if !env.storage().persistent().has(&key) {
+        return Err(RbacError::FunctionNotMapped);
+    }
+    env.storage().persistent().remove(&key);
+    bump_revocation_epoch(env);
+
+    // Emit event
+    events::function_role_removed(env, target.clone(), fn_name.clone());
+    Ok(())
+}
+
+// =============================================================================
+// Federation
+// =============================================================================
+
+/// Let `source_role` in a trusted external RBAC contract satisfy `role` here.
+///
+/// # Authorization
+/// Only callable by account with DEFAULT_ADMIN_ROLE.
+///
+/// # Errors
+/// - `RoleNotFound` if role does not exist
+/// - `InvalidFederation` if `source_rbac` is this contract
+/// - `TooManyFederatedSources` if role already has `MAX_FEDERATED_SOURCES` sources
+///
+/// # Note
+/// Federated roles satisfy `has_role` only. They never confer admin rights here.
+pub fn add_federated_role(
+    env: &Env,
+    caller: &Address,
+    role: &Symbol,
+    source_rbac: &Address,
+    source_role: &Symbol,
+) -> Result<(), RbacError> {
+    // Only DEFAULT_ADMIN_ROLE can manage federation
+    require_admin(env, &DEFAULT_ADMIN_ROLE, caller)?;
+
+    // Validate role exists
+    require_role_exists(env, role)?;
+
+    // Calling ourselves would be contract re-entry
+    if *source_rbac == env.current_contract_address() {
+        return Err(RbacError::InvalidFederation);
+    }
+
+    let source = FederatedRole {
+        rbac: source_rbac.clone(),
+        role: source_role.clone(),
+    };
+    let mut sources = get_federated_roles(env, role);
+    if sources.contains(&source) {
+        return Ok(());
+    }
+    if sources.len() >= MAX_FEDERATED_SOURCES {
+        return Err(RbacError::TooManyFederatedSources);
+    }
+    sources.push_back(source);
+    env.storage()
+        .persistent()
+        .set(&DataKey::FederatedRoles(role.clone()), &sources);
+
+    // Emit event
+    events::federated_role_added(env, role.clone(), source_rbac.clone(), source_role.clone());
+    Ok(())
+}
+
+/// Stop accepting `source_role` from an external RBAC contract for `role`.
+///
+/// # Authorization
+/// Only callable by account with DEFAULT_ADMIN_ROLE.
+///
+/// # Errors
+/// - `FederationNotFound` if the source is not federated for `role`
+pub fn remove_federated_role(
+    env: &Env,
+    caller: &Address,
+    role: &Symbol,
+    source_rbac: &Address,
+    source_role: &Symbol,
+) -> Result<(), RbacError> {
+    // Only DEFAULT_ADMIN_ROLE can manage federation
+    require_admin(env, &DEFAULT_ADMIN_ROLE, caller)?;
+
+    let source = FederatedRole {
+        rbac: source_rbac.clone(),
+        role: source_role.clone(),
+    };
+    let mut sources = get_federated_roles(env, role);
+    let Some(index) = sources.first_index_of(&source) else {
+        return Err(RbacError::FederationNotFound);
+    };
+    sources.remove(index);
+    if sources.is_empty() {
+        env.storage()
+            .persistent()
+            .remove(&DataKey::FederatedRoles(role.clone()));
+    } else {
+        env.storage()
+            .persistent()
+            .set(&DataKey::FederatedRoles(role.clone()), &sources);
+    }
+    bump_revocation_epoch(env);
+
+    // Emit event
+    events::federated_role_removed(env, role.clone(), source_rbac.clone(), source_role.clone());
+    Ok(())
+}
+
+// =============================================================================
+// Role Grants
+// =============================================================================
+
+/// Grant a role to an account with optional expiry.
+///
+/// # Authorization
+/// Caller must have the admin role for this role.
+///
+/// # Errors
+/// - `RoleNotFound` if role does not exist
+/// - `InvalidExpiry` if expiry is non-zero and in the past
+/// - `ExceedsMaxDuration` if the role has a max duration and expiry is 0 or too far out
+/// - `ConflictingRole` if account holds an unexpired role that conflicts with this one
+/// - `MaxMembersReached` if account is a new member and the role is at its member cap
+pub fn grant_role(
+    env: &Env,
+    caller: &Address,
+    role: &Symbol,
+    account: &Address,
+    expiry: u64,
+) -> Result<(), RbacError> {
+    grant_role_scheduled(env, caller, role, account, 0, expiry)
+}
+
+/// Grant a role to a deployed contract, such as a keeper or another protocol.
+///
+/// # Authorization
+/// Caller must have the admin role for this role.
+///
+/// # Errors
+/// - `NotAContract` if `contract` is not a deployed Wasm contract
+/// - Any error returned by `grant_role`
+///
+/// # Note
+/// A contract authorizes a call by invoking it directly, so role checks that need
+/// its auth (`authorize`, `check_call`) pass only when it is the direct invoker, or
+/// when it pre-authorized the nested call with `authorize_as_current_contract`.
+pub fn grant_role_to_contract(
+    env: &Env,
+    caller: &Address,
+    role: &Symbol,
+    contract: &Address,
+    expiry: u64,
+) -> Result<(), RbacError> {
+    if !is_contract(contract) {
+        return Err(RbacError::NotAContract);
+    }
+    grant_role(env, caller, role, contract, expiry)
+}
+
+/// Grant a role to an account that only becomes active at `valid_from`.
+///
+/// # Authorization
+/// Caller must have the admin role for this role.
+///
+/// # Errors
+/// - `RoleNotFound` if role does not exist
+/// - `InvalidExpiry` if expiry is non-zero and in the past, or not after valid_from
+/// - `ExceedsMaxDuration` if the role has a max duration and the active window is
+///   open-ended or too long
+/// - `ConflictingRole` if account holds an unexpired role that conflicts with this one
+/// - `MaxMembersReached` if account is a new member and the role is at its member cap
+///
+/// # Note
+/// Emits `RoleScheduled` instead of `RoleGranted` when valid_from is in the future.
+pub fn grant_role_scheduled(
+    env: &Env,
+    caller: &Address,
+    role: &Symbol,
+    account: &Address,
+    valid_from: u64,
+    expiry: u64,
+) -> Result<(), RbacError> {
+    // Validate role exists
+    require_role_exists(env, role)?;
+
+    // Get admin role for this role
+    let admin_role: Symbol = env
+        .storage()
+        .persistent()
+        .get(&DataKey::RoleAdmin(role.clone()))
+        .unwrap_or(DEFAULT_ADMIN_ROLE);
+
+    // Caller must have admin role — caller is the granter
+    require_admin(env, &admin_role, caller)?;
+
+    // Validate grant window against the clock and the role's policy
+    validate_expiry(env, role, valid_from, expiry)?;
+
+    // Enforce separation of duties against roles the account already holds
+    if !get_conflicting_roles(env, role, account).is_empty() {
+        return Err(RbacError::ConflictingRole);
+    }
+
+    // Enforce the role's member cap (re-grants to existing members are not counted)
+    let is_member: bool = env
+        .storage()
+        .persistent()
+        .get(&DataKey::RoleMember(role.clone(), account.clone()))
+        .unwrap_or(false);
+    let member_count = get_role_member_count(env, role);
+    if !is_member {
+        require_below_member_cap(env, role)?;
+    }
+
+    // Set membership
+    env.storage()
+        .persistent()
+        .set(&DataKey::RoleMember(role.clone(), account.clone()), &true);
+    if !is_member {
+        env.storage()
+            .persistent()
+            .set(&DataKey::RoleMemberCount(role.clone()), &(member_count + 1));
+    } else {
+        // A re-grant can shorten or postpone an active grant
+        bump_revocation_epoch(env);
+    }
+
+    // Set expiry
+    env.storage()
+        .persistent()
+        .set(&DataKey::RoleExpiry(role.clone(), account.clone()), &expiry);
+
+    // Set activation time (only stored for future-dated grants)
+    let valid_from_key = DataKey::RoleValidFrom(role.clone(), account.clone());
+    if valid_from > env.ledger().timestamp() {
+        env.storage().persistent().set(&valid_from_key, &valid_from);
+
+        // Emit event with correct granter identity
+        events::role_scheduled(
+            env,
+            role.clone(),
+            account.clone(),
+            valid_from,
+            expiry,
+            caller.clone(),
+        );
+    } else {
+        env.storage().persistent().remove(&valid_from_key);
+
+        // Emit event with correct granter identity
+        events::role_granted(env, role.clone(), account.clone(), expiry, caller.clone());
+    }
+
+    Ok(())
+}
+
+/// Extend an existing role grant to a later expiry.
+///
+/// # Authorization
+/// Caller must have the admin role for this role.
+///
+/// # Errors
+/// - `RoleNotFound` if role does not exist
+/// - `NotMember` if account has no grant for this role
+/// - `InvalidExpiry` if the grant has already expired, or new_expiry is in the past or
+///   not later than the current expiry
+/// - `ExceedsMaxDuration` if the role has a max duration and new_expiry is 0 or too far out
+/// - `ConflictingRole` if account holds a role that is mutually exclusive with this one
+///
+/// # Note
+/// Emits `RoleExtended` instead of `RoleGranted` so indexers can tell renewals apart.
+/// An expired grant must be granted again, which re-runs every grant check.
+pub fn extend_role(
+    env: &Env,
+    caller: &Address,
+    role: &Symbol,
+    account: &Address,
+    new_expiry: u64,
+) -> Result<(), RbacError> {
+    // Validate role exists
+    require_role_exists(env, role)?;
+
+    // Get admin role for this role
+    let admin_role: Symbol = env
+        .storage()
+        .persistent()
+        .get(&DataKey::RoleAdmin(role.clone()))
+        .unwrap_or(DEFAULT_ADMIN_ROLE);
+
+    // Caller must have admin role — caller is the extender
+    require_admin(env, &admin_role, caller)?;
+
+    // Only existing grants can be extended
+    let is_member: bool = env
+        .storage()
+        .persistent()
+        .get(&DataKey::RoleMember(role.clone(), account.clone()))
+        .unwrap_or(false);
+    if !is_member {
+        return Err(RbacError::NotMember);
+    }
+
+    // New expiry must be strictly later (0 = never, which is later than any timestamp)
+    let old_expiry: u64 = env
+        .storage()
+        .persistent()
+        .get(&DataKey::RoleExpiry(role.clone(), account.clone()))
+        .unwrap_or(0);
+    if old_expiry == 0 || (new_expiry != 0 && new_expiry <= old_expiry) {
+        return Err(RbacError::InvalidExpiry);
+    }
+
+    // An expired grant cannot be revived: it may have been superseded by a conflicting role
+    if env.ledger().timestamp() >= old_expiry {
+        return Err(RbacError::InvalidExpiry);
+    }
+
+    // Validate expiry against the clock and the role's policy
+    let valid_from = get_role_valid_from(env, role, account);
+    validate_expiry(env, role, valid_from, new_expiry)?;
+
+    // Enforce separation of duties, as for a new grant
+    if !get_conflicting_roles(env, role, account).is_empty() {
+        return Err(RbacError::ConflictingRole);
+    }
+
+    // Update expiry
+    env.storage().persistent().set(
+        &DataKey::RoleExpiry(role.clone(), account.clone()),
+        &new_expiry,
+    );
+
+    // Emit event with both bounds for indexers
+    events::role_extended(
+        env,
+        role.clone(),
+        account.clone(),
+        old_expiry,
+        new_expiry,
+        caller.clone(),
+    );
+
+    Ok(())
+}
+
+/// Revoke a role from an account.
+///
+/// # Authorization
+/// Caller must have the admin role for this role.
+///
+/// # Errors
+/// - `RoleNotFound` if role does not exist
+pub fn revoke_role(
+    env: &Env,
+    caller: &Address,
+    role: &Symbol,
+    account: &Address,
+) -> Result<(), RbacError> {
+    // Validate role exists
+    require_role_exists(env, role)?;
+
+    // Get admin role for this role
+    let admin_role: Symbol = env
+        .storage()
+        .persistent()
+        .get(&DataKey::RoleAdmin(role.clone()))
+        .unwrap_or(DEFAULT_ADMIN_ROLE);
+
+    // Caller must have admin role — caller is the revoker
+    require_admin(env, &admin_role, caller)?;
+
+    // Remove membership and expiry
+    remove_member(env, role, account);
+
+    // Emit event with correct revoker identity
+    events::role_revoked(env, role.clone(), account.clone(), caller.clone());
+    Ok(())
+}
+
+/// Give up a role held by the caller.
+///
+/// # Authorization
+/// Account must authorize; no admin role is required.
+///
+/// # Errors
+/// - `RoleNotFound` if role does not exist
+/// - `NotMember` if account has no grant for this role
+///
+/// # Warning
+/// Renouncing the last `DEFAULT_ADMIN_ROLE` grant freezes administration.
+pub fn renounce_role(env: &Env, account: &Address, role: &Symbol) -> Result<(), RbacError> {
+    // Require cryptographic proof that account controls this address
+    account.require_auth();
+
+    // Validate role exists
+    require_role_exists(env, role)?;
+
+    // Remove membership and expiry
+    if !remove_member(env, role, account) {
+        return Err(RbacError::NotMember);
+    }
+
+    // Emit event with the account as its own revoker
+    events::role_revoked(env, role.clone(), account.clone(), account.clone());
+    Ok(())
+}
+
+// =============================================================================
+// Delegation
+// =============================================================================
+
+/// Let a role holder temporarily share the role with another account.
+///
+/// # Authorization
+/// Delegator must authorize and hold the role through a direct grant. Delegates
+/// cannot re-delegate, and a delegated role never confers admin rights.
+///
+/// # Errors
+/// - `RoleNotFound` if role does not exist
+/// - `NotAuthorized` if delegator does not directly hold the role
+/// - `InvalidDelegation` if delegator == delegate
+/// - `InvalidExpiry` if expiry is 0 or in the past
+/// - `ExceedsMaxDuration` if the role has a max duration and expiry is too far out
+/// - `ConflictingRole` if delegate holds a role that is mutually exclusive with this one
+/// - `MaxMembersReached` if delegate has no delegation of the role and the role is at
+///   its member cap
+/// - `TooManyDelegates` if delegator already has `MAX_DELEGATES` unexpired delegates for
+///   the role
+///
+/// # Note
+/// The expiry is capped at the delegator's own expiry. The delegator's expired
+/// delegations are removed first. Replaces any delegation the delegate already has
+/// for this role. Revoking, renouncing or cleaning up the
+/// delegator's grant removes its delegations.
+pub fn delegate_role(
+    env: &Env,
+    delegator: &Address,
+    role: &Symbol,
+    delegate: &Address,
+    expiry: u64,
+) -> Result<(), RbacError> {
+    // Only a direct holder can delegate
+    require_admin(env, role, delegator)?;
+
+    require_role_exists(env, role)?;
+
+    if delegator == delegate {
+        return Err(RbacError::InvalidDelegation);
+    }
+
+    // Delegations are always temporary
+    if expiry == 0 || expiry <= env.ledger().timestamp() {
+        return Err(RbacError::InvalidExpiry);
+    }
+
+    // Cap at the delegator's own expiry
+    let delegator_expiry = get_role_expiry(env, role, delegator);
+    let expiry = if delegator_expiry != 0 && expiry > delegator_expiry {
+        delegator_expiry
+    } else {
+        expiry
+    };
+    validate_expiry(env, role, 0, expiry)?;
+
+    // Enforce separation of duties for the delegate too
+    if !get_conflicting_roles(env, role, delegate).is_empty() {
+        return Err(RbacError::ConflictingRole);
+    }
+
+    // Bound the delegator's delegates; expired delegations are dropped first, and
+    // re-delegating to a current delegate is not counted
+    let mut delegates = prune_expired_delegates(env, role, delegator);
+    let is_delegate = delegates.contains(delegate);
+    if !is_delegate && delegates.len() >= MAX_DELEGATES {
+        return Err(RbacError::TooManyDelegates);
+    }
+
+    // Replace an existing delegation from another delegator, or count a new one
+    // toward the role's member cap
+    match get_delegation(env, role, delegate) {
+        Some(existing) => unindex_delegate(env, role, &existing.delegator, delegate),
+        None => {
+            require_below_member_cap(env, role)?;
+            set_delegation_count(env, role, get_delegation_count(env, role) + 1);
+        }
+    }
+
+    env.storage().persistent().set(
+        &DataKey::Delegation(role.clone(), delegate.clone()),
+        &Delegation {
+            delegator: delegator.clone(),
+            expiry,
+        },
+    );
+    if !is_delegate {
+        delegates.push_back(delegate.clone());
+        env.storage().persistent().set(
+            &DataKey::Delegates(role.clone(), delegator.clone()),
+            &delegates,
+        );
+    }
+
+    // Emit event
+    events::role_delegated(
+        env,
+        role.clone(),
+        delegator.clone(),
+        delegate.clone(),
+        expiry,
+    );
+    Ok(())
+}
+
+/// Withdraw a delegation before it expires.
+///
+/// # Authorization
+/// Delegator must authorize.
+///
+/// # Errors
+/// - `DelegationNotFound` if delegate has no delegation of this role from delegator
+pub fn revoke_delegation(
+    env: &Env,
+    delegator: &Address,
+    role: &Symbol,
+    delegate: &Address,
+) -> Result<(), RbacError> {
+    // Require cryptographic proof that delegator controls this address
+    delegator.require_auth();
+
+    match get_delegation(env, role, delegate) {
+        Some(delegation) if delegation.delegator == *delegator => {}
+        _ => return Err(RbacError::DelegationNotFound),
+    }
+
+    remove_delegation(env, role, delegate);
+    unindex_delegate(env, role, delegator, delegate);
+    bump_revocation_epoch(env);
+
+    // Emit event
+    events::delegation_revoked(env, role.clone(), delegator.clone(), delegate.clone());
+    Ok(())
+}
+
+// =============================================================================
+// Role Checks
+// =============================================================================
+
+/// Check if an account has a specific role (pure, no state mutation).
+///
+/// # Returns
+/// `true` if the account has the role locally (see `has_local_role`) or holds a
+/// federated source role in a trusted external RBAC contract, `false` otherwise.
+///
+/// # Note
+/// This is a pure read function. Use `cleanup_expired_role` to remove expired grants.
+/// Federated sources are only queried when the local check fails, with at most
+/// `MAX_FEDERATED_SOURCES` calls to `has_local_role`. A failing source counts as `false`.
+pub fn has_role(env: &Env, role: &Symbol, account: &Address) -> bool {
+    has_local_role(env, role, account) || has_federated_role(env, role, account)
+}
+
+/// Check if an account has a role in this contract, without following federation.
+///
+/// # Returns
+/// `true` if the account has an active grant (`valid_from <= now < expiry`) or an
+/// unexpired delegation from an account with an active grant, `false` otherwise.
+pub fn has_local_role(env: &Env, role: &Symbol, account: &Address) -> bool {
+    has_direct_role(env, role, account) || has_delegated_role(env, role, account)
+}
+
+/// Check if an account has an active grant of a role, ignoring delegations.
+fn has_direct_role(env: &Env, role: &Symbol, account: &Address) -> bool {
+    // Check membership
+    let is_member: bool = env
+        .storage()
+        .persistent()
+        .get(&DataKey::RoleMember(role.clone(), account.clone()))
+        .unwrap_or(false);
+
+    if !is_member {
+        return false;
+    }
+
+    // Check activation: scheduled grants are inactive until valid_from
+    let current_time = env.ledger().timestamp();
+    if current_time < get_role_valid_from(env, role, account) {
+        return false;
+    }
+
+    // Check expiry
+    let expiry: u64 = env
+        .storage()
+        .persistent()
+        .get(&DataKey::RoleExpiry(role.clone(), account.clone()))
+        .unwrap_or(0);
+
+    // 0 means never expires
+    if expiry == 0 {
+        return true;
+    }
+
+    // Expiry is exclusive: role valid while current_time < expiry
+    current_time < expiry
+}
+
+/// Cleanup an expired role grant, removing it from storage.
+///
+/// # Returns
+/// `true` if the role was expired and cleaned up, `false` if still valid or not a member.
+///
+/// # Note
+/// Emits `RoleExpired` event if the role was expired and removed.
+pub fn cleanup_expired_role(env: &Env, role: &Symbol, account: &Address) -> bool {
+    // Check membership
+    let is_member: bool = env
+        .storage()
+        .persistent()
+        .get(&DataKey::RoleMember(role.clone(), account.clone()))
+        .unwrap_or(false);
+
+    if !is_member {
+        return false;
+    }
+
+    // Check expiry
+    let expiry: u64 = env
+        .storage()
+        .persistent()
+        .get(&DataKey::RoleExpiry(role.clone(), account.clone()))
+        .unwrap_or(0);
+
+    // 0 means never expires
+    if expiry == 0 {
+        return false;
+    }
+
+    // Check if expired (same semantics: current_time >= expiry means expired)
+    let current_time = env.ledger().timestamp();
+    if current_time >= expiry {
+        // Clean up expired membership
+        remove_member(env, role, account);
+
+        // Emit expiry event
+        events::role_expired(env, role.clone(), account.clone(), expiry);
+        return true;
+    }
+
+    false
+}
+
+/// Cleanup an expired delegation, removing it from storage.
+///
+/// # Returns
+/// `true` if the delegation was expired and cleaned up, `false` if still valid or absent.
+///
+/// # Note
+/// Anyone can call this. It frees the delegation's slot in the role's member cap and in
+/// its delegator's `MAX_DELEGATES`, and emits `DelegationRevoked`.
+pub fn cleanup_expired_delegation(env: &Env, role: &Symbol, delegate: &Address) -> bool {
+    let Some(delegation) = get_delegation(env, role, delegate) else {
+        return false;
+    };
+    if env.ledger().timestamp() < delegation.expiry {
+        return false;
+    }
+
+    remove_delegation(env, role, delegate);
+    unindex_delegate(env, role, &delegation.delegator, delegate);
+
+    // Emit event
+    events::delegation_revoked(env, role.clone(), delegation.delegator, delegate.clone());
+    true
+}
+
+/// Check if an account has a specific role, returning an error if not.
+///
+/// # Returns
+/// `Ok(())` if the account has the role, `Err(NotAuthorized)` otherwise.
+///
+/// # Note
+/// When called via the generated client, the error will cause a panic.
+pub fn require_role(env: &Env, role: &Symbol, account: &Address) -> Result<(), RbacError> {
+    if !has_role(env, role, account) {
+        return Err(RbacError::NotAuthorized);
+    }
+    Ok(())
+}
+
+/// Require the account's signature and check that it has a specific role.
+///
+/// # Returns
+/// `Ok(())` if the account authorized the call and has the role, `Err(NotAuthorized)`
+/// if it lacks the role.
+///
+/// # Authorization
+/// Account must authorize. Delegated roles count; admin functions use direct grants
+/// only.
+pub fn authorize(env: &Env, role: &Symbol, account: &Address) -> Result<(), RbacError> {
+    // Require cryptographic proof that account controls this address
+    account.require_auth();
+
+    require_role(env, role, account)
+}
+
+/// Require the account's signature and check that it has the role mapped to
+/// `target.fn_name`.
+///
+/// # Errors
+/// - `FunctionNotMapped` if no role is mapped to the function
+/// - `NotAuthorized` if the account lacks the mapped role
+///
+/// # Authorization
+/// Account must authorize.
+pub fn check_call(
+    env: &Env,
+    target: &Address,
+    fn_name: &Symbol,
+    account: &Address,
+) -> Result<(), RbacError> {
+    let role = get_function_role(env, target, fn_name).ok_or(RbacError::FunctionNotMapped)?;
+    authorize(env, &role, account)
+}
+
+// =============================================================================
+// Getters
+// =============================================================================
+
+/// Get the expiry timestamp for a role grant.
+///
+/// # Returns
+/// The expiry timestamp (0 = never expires), or 0 if not a member.
+pub fn get_role_expiry(env: &Env, role: &Symbol, account: &Address) -> u64 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::RoleExpiry(role.clone(), account.clone()))
+        .unwrap_or(0)
+}
+
+/// Get both bounds of a role grant.
+///
+/// # Returns
+/// The grant's `valid_from` and `expiry`, or `None` if not a member.
+pub fn get_role_grant(env: &Env, role: &Symbol, account: &Address) -> Option<RoleGrant> {
+    let is_member: bool = env
+        .storage()
+        .persistent()
+        .get(&DataKey::RoleMember(role.clone(), account.clone()))
+        .unwrap_or(false);
+
+    if !is_member {
+        return None;
+    }
+
+    Some(RoleGrant {
+        valid_from: get_role_valid_from(env, role, account),
+        expiry: get_role_expiry(env, role, account),
+    })
+}
+
+/// Get the number of accounts holding a role.
+///
+/// # Returns
+/// The count of stored grants, including expired grants not yet cleaned up.
+pub fn get_role_member_count(env: &Env, role: &Symbol) -> u32 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::RoleMemberCount(role.clone()))
+        .unwrap_or(0)
+}
+
+/// Get the roles that are mutually exclusive with a role.
+///
+/// # Returns
+/// The conflicting roles, or an empty list if none are configured.
+pub fn get_role_conflicts(env: &Env, role: &Symbol) -> Vec<Symbol> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::RoleConflicts(role.clone()))
+        .unwrap_or(Vec::new(env))
+}
+
+/// List the roles held by an account that conflict with a given role.
+///
+/// # Returns
+/// Every role conflicting with `role` that `account` holds unexpired (including
+/// scheduled grants and active delegations). Empty if `role` could be granted
+/// without a conflict.
+pub fn get_conflicting_roles(env: &Env, role: &Symbol, account: &Address) -> Vec<Symbol> {
+    let mut held = Vec::new(env);
+    for other in get_role_conflicts(env, role) {
+        if holds_unexpired(env, &other, account) {
+            held.push_back(other);
+        }
+    }
+    held
+}
+
+/// Get the delegation of a role held by `delegate`.
+///
+/// # Returns
+/// The delegator and expiry, or `None` if there is no delegation. The delegation may
+/// be expired or its delegator may have lost the role; use `has_role` to check it.
+pub fn get_delegation(env: &Env, role: &Symbol, delegate: &Address) -> Option<Delegation> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Delegation(role.clone(), delegate.clone()))
+}
+
+/// Get the external sources that can satisfy a role.
+///
+/// # Returns
+/// The (RBAC contract, role) pairs, or an empty list if the role is not federated.
+pub fn get_federated_roles(env: &Env, role: &Symbol) -> Vec<FederatedRole> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::FederatedRoles(role.clone()))
+        .unwrap_or(Vec::new(env))
+}
+
+/// Get the role mapped to `target.fn_name`.
+///
+/// # Returns
+/// The required role, or `None` if the function is not mapped.
+pub fn get_function_role(env: &Env, target: &Address, fn_name: &Symbol) -> Option<Symbol> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::FunctionRole(target.clone(), fn_name.clone()))
+}
+
+/// Get the admin role for a role.
+///
+/// # Returns
+/// The admin role symbol, or DEFAULT_ADMIN_ROLE if role doesn't exist.
+pub fn get_role_admin(env: &Env, role: &Symbol) -> Symbol {
+    env.storage()
+        .persistent()
+        .get(&DataKey::RoleAdmin(role.clone()))
+        .unwrap_or(DEFAULT_ADMIN_ROLE)
+}
+
+/// Get the grant policy for a role.
+///
+/// # Returns
+/// The role's policy, or the unrestricted default if none is set.
+pub fn get_role_policy(env: &Env, role: &Symbol) -> RolePolicy {
+    env.storage()
+        .persistent()
+        .get(&DataKey::RolePolicy(role.clone()))
+        .unwrap_or_default()
+}
+
+/// Check if a role exists.
+///
+/// # Returns
+/// `true` if the role has been created, `false` otherwise.
+pub fn role_exists(env: &Env, role: &Symbol) -> bool {
+    env.storage()
+        .persistent()
+        .has(&DataKey::RoleExists(role.clone()))
+}
+
+/// Get the deployer address.
+///
+/// # Note
+/// Returns the address that initialized the contract.
+pub fn get_deployer(env: &Env) -> Option<Address> {
+    env.storage().persistent().get(&DataKey::Deployer)
+}
+
+/// Get the revocation epoch.
+///
+/// # Note
+/// Incremented by every revoke, renounce, expired-grant cleanup, delegation
+/// revocation, federated source removal, re-grant, and function mapping change or
+/// removal. Consumers caching positive
+/// role checks discard them when the epoch changes.
+pub fn get_revocation_epoch(env: &Env) -> u64 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::RevocationEpoch)
+        .unwrap_or(0)
+}
+
+// =============================================================================
+// Internal Helpers
+// =============================================================================
+
+/// Check that a role exists.
+///
+/// # Returns
+/// `Ok(())` if role exists, `Err(RoleNotFound)` otherwise.
+fn require_role_exists(env: &Env, role: &Symbol) -> Result<(), RbacError> {
+    if !env
+        .storage()
+        .persistent()
+        .has(&DataKey::RoleExists(role.clone()))
+    {
+        return Err(RbacError::RoleNotFound);
+    }
+    Ok(())
+}
+
+/// Check if an account has a grant for a role that has not expired yet, or holds it
+/// through an active delegation.
+///
+/// # Note
+/// Unlike `has_role`, this ignores `valid_from`: scheduled grants count as held.
+fn holds_unexpired(env: &Env, role: &Symbol, account: &Address) -> bool {
+    let is_member: bool = env
+        .storage()
+        .persistent()
+        .get(&DataKey::RoleMember(role.clone(), account.clone()))
+        .unwrap_or(false);
+
+    if is_member {
+        let expiry = get_role_expiry(env, role, account);
+        if expiry == 0 || env.ledger().timestamp() < expiry {
+            return true;
+        }
+    }
+
+    has_delegated_role(env, role, account)
+}
+
+/// Remove a grant from storage and decrement the role's member count.
+///
+/// # Returns
+/// `true` if the account was a member, `false` if there was nothing to remove.
+fn remove_member(env: &Env, role: &Symbol, account: &Address) -> bool {
+    let member_key = DataKey::RoleMember(role.clone(), account.clone());
+    if !env.storage().persistent().has(&member_key) {
+        return false;
+    }
+
+    env.storage().persistent().remove(&member_key);
+    env.storage()
+        .persistent()
+        .remove(&DataKey::RoleExpiry(role.clone(), account.clone()));
+    env.storage()
+        .persistent()
+        .remove(&DataKey::RoleValidFrom(role.clone(), account.clone()));
+
+    let member_count = get_role_member_count(env, role);
+    env.storage().persistent().set(
+        &DataKey::RoleMemberCount(role.clone()),
+        &member_count.saturating_sub(1),
+    );
+
+    // A former holder's delegations end with its grant
+    let delegates = get_delegates(env, role, account);
+    for delegate in delegates.iter() {
+        env.storage()
+            .persistent()
+            .remove(&DataKey::Delegation(role.clone(), delegate.clone()));
+        events::delegation_revoked(env, role.clone(), account.clone(), delegate);
+    }
+    if !delegates.is_empty() {
+        let count = get_delegation_count(env, role).saturating_sub(delegates.len());
+        set_delegation_count(env, role, count);
+    }
+    env.storage()
+        .persistent()
+        .remove(&DataKey::Delegates(role.clone(), account.clone()));
+    bump_revocation_epoch(env);
+    true
+}
+
+/// Check if an account holds a role through an unexpired delegation whose delegator
+/// still has an active grant.
+fn has_delegated_role(env: &Env, role: &Symbol, delegate: &Address) -> bool {
+    match get_delegation(env, role, delegate) {
+        Some(delegation) => {
+            env.ledger().timestamp() < delegation.expiry
+                && has_direct_role(env, role, &delegation.delegator)
+        }
+        None => false,
+    }
+}
+
+/// Check if an account holds a federated source role in any trusted external RBAC
+/// contract. Calls `has_local_role` so resolution never goes more than one hop.
+fn has_federated_role(env: &Env, role: &Symbol, account: &Address) -> bool {
+    get_federated_roles(env, role).iter().any(|source| {
+        let args = vec![env, source.role.into_val(env), account.into_val(env)];
+        matches!(
+            env.try_invoke_contract::<bool, soroban_sdk::Error>(
+                &source.rbac,
+                &Symbol::new(env, "has_local_role"),
+                args,
+            ),
+            Ok(Ok(true))
+        )
+    })
+}
+
+/// Get the number of stored delegations of a role, including expired ones.
+fn get_delegation_count(env: &Env, role: &Symbol) -> u32 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::DelegationCount(role.clone()))
+        .unwrap_or(0)
+}
+
+/// Store the number of delegations of a role.
+fn set_delegation_count(env: &Env, role: &Symbol, count: u32) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::DelegationCount(role.clone()), &count);
+}
+
+/// Check that one more grant or delegation fits under the role's member cap.
+///
+/// # Returns
+/// `Ok(())` if the role is uncapped or below its cap, `Err(MaxMembersReached)` otherwise.
+fn require_below_member_cap(env: &Env, role: &Symbol) -> Result<(), RbacError> {
+    let policy = get_role_policy(env, role);
+    if policy.max_members == 0 {
+        return Ok(());
+    }
+    let held = get_role_member_count(env, role).saturating_add(get_delegation_count(env, role));
+    if held >= policy.max_members {
+        return Err(RbacError::MaxMembersReached);
+    }
+    Ok(())
+}
+
+/// Get the accounts a delegator has delegated a role to.
+fn get_delegates(env: &Env, role: &Symbol, delegator: &Address) -> Vec<Address> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Delegates(role.clone(), delegator.clone()))
+        .unwrap_or(Vec::new(env))
+}
+
+/// Remove a delegation and release its slot in the role's member cap. The caller
+/// updates the delegator's list of delegates.
+fn remove_delegation(env: &Env, role: &Symbol, delegate: &Address) {
+    env.storage()
+        .persistent()
+        .remove(&DataKey::Delegation(role.clone(), delegate.clone()));
+    set_delegation_count(env, role, get_delegation_count(env, role).saturating_sub(1));
+}
+
+/// Remove the delegator's expired delegations and return its remaining delegates.
+fn prune_expired_delegates(env: &Env, role: &Symbol, delegator: &Address) -> Vec<Address> {
+    let now = env.ledger().timestamp();
+    let delegates = get_delegates(env, role, delegator);
+    let mut active = Vec::new(env);
+    for delegate in delegates.iter() {
+        match get_delegation(env, role, &delegate) {
+            Some(delegation) if now >= delegation.expiry => {
+                remove_delegation(env, role, &delegate);
+                events::delegation_revoked(env, role.clone(), delegator.clone(), delegate);
+            }
+            _ => active.push_back(delegate),
+        }
+    }
+    if active.len() != delegates.len() {
+        env.storage().persistent().set(
+            &DataKey::Delegates(role.clone(), delegator.clone()),
+            &active,
+        );
+    }
+    active
+}
+
+/// Remove `delegate` from the delegator's list of delegates.
+fn unindex_delegate(env: &Env, role: &Symbol, delegator: &Address, delegate: &Address) {
+    let mut delegates = get_delegates(env, role, delegator);
+    if let Some(index) = delegates.first_index_of(delegate) {
+        delegates.remove(index);
+        env.storage().persistent().set(
+            &DataKey::Delegates(role.clone(), delegator.clone()),
+            &delegates,
+        );
+    }
+}
+
+/// Add `other` to the conflict list of `role` if not already present.
+fn insert_conflict(env: &Env, role: &Symbol, other: &Symbol) {
+    let mut conflicts = get_role_conflicts(env, role);
+    if !conflicts.contains(other) {
+        conflicts.push_back(other.clone());
+        env.storage()
+            .persistent()
+            .set(&DataKey::RoleConflicts(role.clone()), &conflicts);
+    }
+}
+
+/// Remove `other` from the conflict list of `role`, dropping the key when empty.
+fn delete_conflict(env: &Env, role: &Symbol, other: &Symbol) {
+    let mut conflicts = get_role_conflicts(env, role);
+    if let Some(index) = conflicts.first_index_of(other) {
+        conflicts.remove(index);
+        let key = DataKey::RoleConflicts(role.clone());
+        if conflicts.is_empty() {
+            env.storage().persistent().remove(&key);
+        } else {
+            env.storage().persistent().set(&key, &conflicts);
+        }
+    }
+}
+
+/// Get the activation timestamp for a role grant (0 = active immediately).
+fn get_role_valid_from(env: &Env, role: &Symbol, account: &Address) -> u64 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::RoleValidFrom(role.clone(), account.clone()))
+        .unwrap_or(0)
+}
+
+/// Validate a grant window for a role.
+///
+/// # Errors
+/// - `InvalidExpiry` if expiry is non-zero and not in the future, or not after valid_from
+/// - `ExceedsMaxDuration` if the role has a max duration and the active window
+///   (from `max(now, valid_from)` to expiry) is open-ended or too long
+fn validate_expiry(
+    env: &Env,
+    role: &Symbol,
+    valid_from: u64,
+    expiry: u64,
+) -> Result<(), RbacError> {
+    let current_time = env.ledger().timestamp();
+
+    // If non-zero, must be in the future (exclusive semantics)
+    // Role valid while current_time < expiry, so expiry must be > current_time
+    if expiry != 0 && expiry <= current_time {
+        return Err(RbacError::InvalidExpiry);
+    }
+
+    // Grant window must be non-empty: valid_from < expiry
+    if expiry != 0 && valid_from >= expiry {
+        return Err(RbacError::InvalidExpiry);
+    }
+
+    // Enforce the role's max duration: grants must be time-boxed within it
+    let start = current_time.max(valid_from);
+    let policy = get_role_policy(env, role);
+    if policy.max_duration != 0 && (expiry == 0 || expiry - start > policy.max_duration) {
+        return Err(RbacError::ExceedsMaxDuration);
+    }
+
+    Ok(())
+}
+
+/// Require the caller's signature and a direct grant of an admin role.
+///
+/// # Authorization
+/// This is the single source of auth for all privileged functions. Delegated roles
+/// do not count, so delegates cannot grant, revoke or re-delegate.
+fn require_admin(env: &Env, role: &Symbol, caller: &Address) -> Result<(), RbacError> {
+    // Require cryptographic proof that caller controls this address
+    caller.require_auth();
+
+    if !has_direct_role(env, role, caller) {
+        return Err(RbacError::NotAuthorized);
+    }
+
+    Ok(())
+}
+
+/// `true` if the address is a deployed Wasm contract.
+fn is_contract(address: &Address) -> bool {
+    matches!(address.executable(), Some(Executable::Wasm(_)))
+}
+
+/// Invalidate role checks cached by consumers.
+fn bump_revocation_epoch(env: &Env) {
+    let epoch = get_revocation_epoch(env);
+    env.storage()
+        .persistent()
+        .set(&DataKey::RevocationEpoch, &epoch.wrapping_add(1));
+}
@@ -14,6 +14,8 @@ pub enum DataKey {
     RoleMember(Symbol, Address),
     /// Maps (role, account) to expiry timestamp (u64, 0 = never expires)
     RoleExpiry(Symbol, Address),
+    /// Maps (role, account) to activation timestamp (u64) — only set for future-dated grants
+    RoleValidFrom(Symbol, Address),
     /// Tracks the deployer/admin address (persistent)
     Deployer,
     /// Boolean flag indicating contract is initialized (persistent, set first during init)
@@ -21,5 +23,62 @@ pub enum DataKey {
     /// Per-role existence marker (persistent) — every created role has this set to true.
     /// This replaces the previous AllRoles Vec to avoid DoS/size limit issues.
     RoleExists(Symbol),
+    /// Maps role symbol to its `RolePolicy` (persistent) — absent means unrestricted
+    RolePolicy(Symbol),
+    /// Maps role symbol to the `Vec<Symbol>` of roles it is mutually exclusive with
+    RoleConflicts(Symbol),
+    /// Maps role symbol to its number of stored members (u32)
+    RoleMemberCount(Symbol),
+    /// Maps (target contract, function name) to the role required to call it
+    FunctionRole(Address, Symbol),
+    /// Maps (role, delegate) to its `Delegation`
+    Delegation(Symbol, Address),
+    /// Maps (role, delegator) to the `Vec<Address>` of its delegates
+    Delegates(Symbol, Address),
+    /// Maps role symbol to its number of stored delegations (u32)
+    DelegationCount(Symbol),
+    /// Maps role symbol to the `Vec<FederatedRole>` sources that can satisfy it
+    FederatedRoles(Symbol),
+    /// Counter bumped whenever a role holder may lose a role (u64, absent = 0)
+    RevocationEpoch,
 }
 
+/// Per-role grant policy, managed by `DEFAULT_ADMIN_ROLE`.
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct RolePolicy {
+    /// Maximum grant lifetime in seconds (0 = unrestricted)
+    pub max_duration: u64,
+    /// Maximum number of grants plus delegations of the role (0 = unrestricted)
+    pub max_members: u32,
+}
+
+/// Bounds of a role grant. The grant is active while `valid_from <= now < expiry`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RoleGrant {
+    /// Activation timestamp (0 = active immediately)
+    pub valid_from: u64,
+    /// Expiry timestamp (0 = never expires)
+    pub expiry: u64,
+}
+
+/// A temporary delegation of a role from a direct holder to another account.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Delegation {
+    /// Account holding the role directly
+    pub delegator: Address,
+    /// Expiry timestamp, capped at the delegator's own expiry
+    pub expiry: u64,
+}
+
+/// A role in a trusted external RBAC contract that satisfies a local role.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FederatedRole {
+    /// The external `RbacContract`
+    pub rbac: Address,
+    /// The role in that contract
+    pub role: Symbol,
+}
@@ -0,0 +1,349 @@
+#![cfg(not(feature = "library"))]
+
+//! Resource budget regression tests.
+//!
+//! Calls every `RbacContract` entry point once in a populated contract and records
+//! what the testutils cost estimate reports for that invocation. The numbers are
+//! compared with a baseline file:
+//! - CPU instructions and memory may grow by at most `TOLERANCE_PERCENT`
+//! - ledger entries read and written, and event bytes, may not grow at all
+//!
+//! Each call is measured twice. The native pass (`tests/budget_baselines.txt`) covers
+//! host work such as storage access, events and auth. The Wasm pass
+//! (`tests/budget_baselines_wasm.txt`) runs the release build of the contract, so it
+//! also includes VM instantiation and execution, which is what fees are charged for.
+//! Build it before running the tests:
+//!
+//! ```bash
+//! cargo build --target wasm32v1-none --release
+//! ```
+//!
+//! Auths are mocked, so signature verification is not included in either pass.
+//!
+//! After an intended change, regenerate the baselines and commit them:
+//!
+//! ```bash
+//! UPDATE_BUDGET_BASELINES=1 cargo test --test budget
+//! ```
+
+use soroban_sdk::testutils::{Address as _, EnvTestConfig, Ledger};
+use soroban_sdk::{symbol_short, Address, Env, Symbol};
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use stellar_keystone_rbac::{RbacContract, RbacContractClient};
+
+/// Allowed growth of CPU instructions and memory over the baseline.
+const TOLERANCE_PERCENT: i64 = 10;
+
+const BASELINES: &str = "tests/budget_baselines.txt";
+const WASM_BASELINES: &str = "tests/budget_baselines_wasm.txt";
+
+/// Release build of the contract, relative to the crate root.
+const WASM: &str = "target/wasm32v1-none/release/stellar_keystone_rbac.wasm";
+
+const MINTER: Symbol = symbol_short!("MINTER");
+const BURNER: Symbol = symbol_short!("BURNER");
+
+/// Resources of one invocation.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Costs {
+    instructions: i64,
+    mem_bytes: i64,
+    read_entries: u32,
+    write_entries: u32,
+    event_bytes: u32,
+}
+
+impl Costs {
+    fn parse(fields: &[&str]) -> Self {
+        let field = |i: usize| fields[i].parse::<i64>().expect("malformed baseline");
+        Costs {
+            instructions: field(0),
+            mem_bytes: field(1),
+            read_entries: field(2) as u32,
+            write_entries: field(3) as u32,
+            event_bytes: field(4) as u32,
+        }
+    }
+
+    /// Describe each resource that regressed against `baseline`.
+    fn regressions(&self, baseline: &Costs) -> Vec<String> {
+        let mut found = Vec::new();
+        let within = |actual: i64, base: i64| actual <= base + base * TOLERANCE_PERCENT / 100;
+        if !within(self.instructions, baseline.instructions) {
+            found.push(format!(
+                "instructions {} -> {}",
+                baseline.instructions, self.instructions
+            ));
+        }
+        if !within(self.mem_bytes, baseline.mem_bytes) {
+            found.push(format!(
+                "mem_bytes {} -> {}",
+                baseline.mem_bytes, self.mem_bytes
+            ));
+        }
+        if self.read_entries > baseline.read_entries {
+            found.push(format!(
+                "read_entries {} -> {}",
+                baseline.read_entries, self.read_entries
+            ));
+        }
+        if self.write_entries > baseline.write_entries {
+            found.push(format!(
+                "write_entries {} -> {}",
+                baseline.write_entries, self.write_entries
+            ));
+        }
+        if self.event_bytes > baseline.event_bytes {
+            found.push(format!(
+                "event_bytes {} -> {}",
+                baseline.event_bytes, self.event_bytes
+            ));
+        }
+        found
+    }
+}
+
+/// Records the costs of each measured call, in call order.
+struct Recorder {
+    env: Env,
+    costs: Vec<(&'static str, Costs)>,
+}
+
+impl Recorder {
+    /// Record the resources of the invocation that just ran under `name`.
+    fn record(&mut self, name: &'static str) {
+        let res = self.env.cost_estimate().resources();
+        self.costs.push((
+            name,
+            Costs {
+                instructions: res.instructions,
+                mem_bytes: res.mem_bytes,
+                read_entries: res.disk_read_entries + res.memory_read_entries,
+                write_entries: res.write_entries,
+                event_bytes: res.contract_events_size_bytes,
+            },
+        ));
+    }
+}
+
+/// Register the contract natively.
+fn register_native(env: &Env) -> Address {
+    env.register(RbacContract, ())
+}
+
+/// Register the release Wasm build of the contract.
+fn register_wasm(env: &Env) -> Address {
+    let path = format!("{}/{}", env!("CARGO_MANIFEST_DIR"), WASM);
+    let wasm = std::fs::read(&path).unwrap_or_else(|_| {
+        panic!("missing {path}, build it with `cargo build --target wasm32v1-none --release`")
+    });
+    env.register(wasm.as_slice(), ())
+}
+
+/// Call every entry point once on contracts created by `register`, recording each
+/// call.
+///
+/// `has_role` is measured twice: a local hit, and a local miss that falls through to
+/// a federated source.
+fn measure(register: fn(&Env) -> Address) -> Vec<(&'static str, Costs)> {
+    let env = Env::new_with_config(EnvTestConfig {
+        capture_snapshot_at_drop: false,
+    });
+    env.mock_all_auths();
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+
+    let admin = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let carol = Address::generate(&env);
+    let dave = Address::generate(&env);
+    let target = Address::generate(&env);
+    let fn_name = symbol_short!("withdraw");
+
+    // A second RBAC contract where `carol` holds MINTER, trusted as a federated source
+    let peer = RbacContractClient::new(&env, &register(&env));
+    peer.initialize(&admin);
+    peer.create_role(&admin, &MINTER, &peer.default_admin_role());
+    peer.grant_role(&admin, &MINTER, &carol, &0);
+
+    let c = RbacContractClient::new(&env, &register(&env));
+    let mut r = Recorder {
+        env: env.clone(),
+        costs: Vec::new(),
+    };
+
+    // Setup and role management
+    c.initialize(&admin);
+    r.record("initialize");
+    let default_admin = c.default_admin_role();
+    r.record("default_admin_role");
+    c.create_role(&admin, &MINTER, &default_admin);
+    r.record("create_role");
+    c.create_role(&admin, &BURNER, &default_admin);
+    c.set_role_admin(&admin, &BURNER, &MINTER);
+    r.record("set_role_admin");
+    c.set_role_max_duration(&admin, &BURNER, &86_400);
+    r.record("set_role_max_duration");
+    c.set_role_max_members(&admin, &MINTER, &10);
+    r.record("set_role_max_members");
+    c.add_role_conflict(&admin, &MINTER, &BURNER);
+    r.record("add_role_conflict");
+    c.set_function_role(&admin, &target, &fn_name, &MINTER);
+    r.record("set_function_role");
+    c.add_federated_role(&admin, &MINTER, &peer.address, &MINTER);
+    r.record("add_federated_role");
+
+    // Grants
+    c.grant_role(&admin, &MINTER, &alice, &0);
+    r.record("grant_role");
+    c.grant_role_scheduled(&admin, &MINTER, &bob, &2_000, &5_000);
+    r.record("grant_role_scheduled");
+    c.grant_role_to_contract(&admin, &MINTER, &peer.address, &0);
+    r.record("grant_role_to_contract");
+    c.extend_role(&admin, &MINTER, &bob, &6_000);
+    r.record("extend_role");
+    c.delegate_role(&alice, &MINTER, &dave, &3_000);
+    r.record("delegate_role");
+
+    // Checks
+    c.has_role(&MINTER, &alice);
+    r.record("has_role");
+    c.has_role(&MINTER, &carol);
+    r.record("has_role_federated");
+    c.has_local_role(&MINTER, &alice);
+    r.record("has_local_role");
+    c.require_role(&MINTER, &alice);
+    r.record("require_role");
+    c.authorize(&MINTER, &alice);
+    r.record("authorize");
+    c.check_call(&target, &fn_name, &alice);
+    r.record("check_call");
+
+    // Getters
+    c.get_role_expiry(&MINTER, &bob);
+    r.record("get_role_expiry");
+    c.get_role_grant(&MINTER, &bob);
+    r.record("get_role_grant");
+    c.get_role_member_count(&MINTER);
+    r.record("get_role_member_count");
+    c.get_role_conflicts(&MINTER);
+    r.record("get_role_conflicts");
+    c.get_conflicting_roles(&BURNER, &alice);
+    r.record("get_conflicting_roles");
+    c.get_delegation(&MINTER, &dave);
+    r.record("get_delegation");
+    c.get_federated_roles(&MINTER);
+    r.record("get_federated_roles");
+    c.get_function_role(&target, &fn_name);
+    r.record("get_function_role");
+    c.get_role_admin(&BURNER);
+    r.record("get_role_admin");
+    c.get_role_policy(&MINTER);
+    r.record("get_role_policy");
+    c.role_exists(&MINTER);
+    r.record("role_exists");
+    c.get_deployer();
+    r.record("get_deployer");
+    c.get_revocation_epoch();
+    r.record("get_revocation_epoch");
+
+    // Removal
+    c.revoke_delegation(&alice, &MINTER, &dave);
+    r.record("revoke_delegation");
+    c.grant_role(&admin, &MINTER, &dave, &1_500);
+    env.ledger().with_mut(|li| li.timestamp = 1_500);
+    c.cleanup_expired_role(&MINTER, &dave);
+    r.record("cleanup_expired_role");
+    c.delegate_role(&alice, &MINTER, &dave, &2_000);
+    env.ledger().with_mut(|li| li.timestamp = 2_000);
+    c.cleanup_expired_delegation(&MINTER, &dave);
+    r.record("cleanup_expired_delegation");
+    c.revoke_role(&admin, &MINTER, &alice);
+    r.record("revoke_role");
+    c.renounce_role(&bob, &MINTER);
+    r.record("renounce_role");
+    c.remove_role_conflict(&admin, &MINTER, &BURNER);
+    r.record("remove_role_conflict");
+    c.remove_function_role(&admin, &target, &fn_name);
+    r.record("remove_function_role");
+    c.remove_federated_role(&admin, &MINTER, &peer.address, &MINTER);
+    r.record("remove_federated_role");
+
+    r.costs
+}
+
+fn baselines_path(file: &str) -> String {
+    format!("{}/{}", env!("CARGO_MANIFEST_DIR"), file)
+}
+
+fn read_baselines(file: &str) -> BTreeMap<String, Costs> {
+    let text = std::fs::read_to_string(baselines_path(file)).expect("missing budget baselines");
+    text.lines()
+        .filter(|line| !line.trim().is_empty() && !line.starts_with('#'))
+        .map(|line| {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            assert_eq!(fields.len(), 6, "malformed baseline: {line}");
+            (fields[0].to_string(), Costs::parse(&fields[1..]))
+        })
+        .collect()
+}
+
+fn write_baselines(file: &str, costs: &[(&'static str, Costs)]) {
+    let mut text = String::from(
+        "# Generated by `UPDATE_BUDGET_BASELINES=1 cargo test --test budget`.\n\
+         # function instructions mem_bytes read_entries write_entries event_bytes\n",
+    );
+    for (name, c) in costs {
+        writeln!(
+            text,
+            "{name} {} {} {} {} {}",
+            c.instructions, c.mem_bytes, c.read_entries, c.write_entries, c.event_bytes
+        )
+        .unwrap();
+    }
+    std::fs::write(baselines_path(file), text).unwrap();
+}
+
+/// Compare `costs` with the baselines in `file`, or rewrite them on request.
+fn check_budgets(file: &str, costs: &[(&'static str, Costs)]) {
+    if std::env::var_os("UPDATE_BUDGET_BASELINES").is_some() {
+        write_baselines(file, costs);
+        return;
+    }
+
+    let mut baselines = read_baselines(file);
+    let mut failures = Vec::new();
+    for (name, actual) in costs {
+        match baselines.remove(*name) {
+            Some(baseline) => {
+                for regression in actual.regressions(&baseline) {
+                    failures.push(format!("{name}: {regression}"));
+                }
+            }
+            None => failures.push(format!("{name}: no baseline")),
+        }
+    }
+    for name in baselines.keys() {
+        failures.push(format!(
+            "{name}: baseline for a call that is no longer measured"
+        ));
+    }
+
+    assert!(
+        failures.is_empty(),
+        "{file}: budget regressions (tolerance {TOLERANCE_PERCENT}% for CPU and memory):\n{}\n\
+         If intended, run `UPDATE_BUDGET_BASELINES=1 cargo test --test budget`.",
+        failures.join("\n")
+    );
+}
+
+#[test]
+fn test_entry_point_budgets() {
+    check_budgets(BASELINES, &measure(register_native));
+}
+
+#[test]
+fn test_wasm_entry_point_budgets() {
+    check_budgets(WASM_BASELINES, &measure(register_wasm));
+}
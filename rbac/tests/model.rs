@@ -0,0 +1,369 @@
+#![cfg(not(feature = "library"))]
+
+//! Stateful property tests: random sequences of role operations and clock jumps run
+//! against `RbacContract` and a plain Rust model of the same state.
+//!
+//! After every step the harness checks that each call returned what the model
+//! predicts, and that the contract still satisfies:
+//! - every role's admin role exists
+//! - `has_role` equals the model for every role and account in the pool
+//! - member counts and expiries equal the model, counting expired grants that
+//!   have not been cleaned up
+
+use proptest::prelude::*;
+use soroban_sdk::testutils::{Address as _, EnvTestConfig, Ledger};
+use soroban_sdk::{symbol_short, Address, Env, Symbol};
+use std::collections::BTreeMap;
+use stellar_keystone_rbac::{RbacContract, RbacContractClient, RbacError};
+
+/// Role pool; index 0 is DEFAULT_ADMIN_ROLE, which exists from `initialize`.
+const ROLES: [Symbol; 4] = [
+    symbol_short!("DEF_ADMIN"),
+    symbol_short!("ALPHA"),
+    symbol_short!("BETA"),
+    symbol_short!("GAMMA"),
+];
+
+/// Size of the account pool; account 0 is the initial admin.
+const ACCOUNTS: usize = 3;
+
+/// Ledger timestamp at the start of every case.
+const START: u64 = 1_000;
+
+/// Expiries and clock jumps are multiples of this, so the clock often lands exactly
+/// on an expiry.
+const STEP: u64 = 100;
+
+#[derive(Clone, Debug)]
+enum Op {
+    CreateRole {
+        caller: usize,
+        role: usize,
+        admin_role: usize,
+    },
+    SetRoleAdmin {
+        caller: usize,
+        role: usize,
+        admin_role: usize,
+    },
+    /// `expiry` is an offset from now; `None` grants without expiry, `Some(0)` is
+    /// already in the past.
+    GrantRole {
+        caller: usize,
+        role: usize,
+        account: usize,
+        expiry: Option<u64>,
+    },
+    RevokeRole {
+        caller: usize,
+        role: usize,
+        account: usize,
+    },
+    CleanupExpiredRole {
+        role: usize,
+        account: usize,
+    },
+    AdvanceTime(u64),
+}
+
+fn op() -> impl Strategy<Value = Op> {
+    let role = 0..ROLES.len();
+    let account = 0..ACCOUNTS;
+    prop_oneof![
+        1 => (account.clone(), role.clone(), role.clone()).prop_map(|(caller, role, admin_role)| {
+            Op::CreateRole {
+                caller,
+                role,
+                admin_role,
+            }
+        }),
+        1 => (account.clone(), role.clone(), role.clone()).prop_map(|(caller, role, admin_role)| {
+            Op::SetRoleAdmin {
+                caller,
+                role,
+                admin_role,
+            }
+        }),
+        3 => (
+            account.clone(),
+            role.clone(),
+            account.clone(),
+            prop::option::of((0u64..30).prop_map(|n| n * STEP))
+        )
+            .prop_map(|(caller, role, account, expiry)| Op::GrantRole {
+                caller,
+                role,
+                account,
+                expiry,
+            }),
+        1 => (account.clone(), role.clone(), account.clone()).prop_map(|(caller, role, account)| {
+            Op::RevokeRole {
+                caller,
+                role,
+                account,
+            }
+        }),
+        1 => (role, account).prop_map(|(role, account)| Op::CleanupExpiredRole { role, account }),
+        1 => (0u64..20).prop_map(|n| Op::AdvanceTime(n * STEP)),
+    ]
+}
+
+/// Reference model of the RBAC state touched by the operations above.
+struct Model {
+    now: u64,
+    /// Maps an existing role to its admin role
+    admins: BTreeMap<usize, usize>,
+    /// Maps (role, account) to the grant's expiry (0 = never), expired or not
+    grants: BTreeMap<(usize, usize), u64>,
+}
+
+impl Model {
+    fn new() -> Self {
+        Model {
+            now: START,
+            admins: BTreeMap::from([(0, 0)]),
+            grants: BTreeMap::from([((0, 0), 0)]),
+        }
+    }
+
+    fn has_role(&self, role: usize, account: usize) -> bool {
+        match self.grants.get(&(role, account)) {
+            Some(&expiry) => expiry == 0 || self.now < expiry,
+            None => false,
+        }
+    }
+
+    fn require_admin(&self, role: usize, caller: usize) -> Result<(), RbacError> {
+        if !self.has_role(role, caller) {
+            return Err(RbacError::NotAuthorized);
+        }
+        Ok(())
+    }
+
+    fn require_role_exists(&self, role: usize) -> Result<(), RbacError> {
+        if !self.admins.contains_key(&role) {
+            return Err(RbacError::RoleNotFound);
+        }
+        Ok(())
+    }
+
+    fn create_role(
+        &mut self,
+        caller: usize,
+        role: usize,
+        admin_role: usize,
+    ) -> Result<(), RbacError> {
+        self.require_admin(0, caller)?;
+        if self.admins.contains_key(&role) {
+            return Err(RbacError::RoleAlreadyExists);
+        }
+        self.require_role_exists(admin_role)?;
+        if role == admin_role && role != 0 {
+            return Err(RbacError::InvalidSelfAdmin);
+        }
+        self.admins.insert(role, admin_role);
+        Ok(())
+    }
+
+    fn set_role_admin(
+        &mut self,
+        caller: usize,
+        role: usize,
+        admin_role: usize,
+    ) -> Result<(), RbacError> {
+        self.require_admin(0, caller)?;
+        self.require_role_exists(role)?;
+        self.require_role_exists(admin_role)?;
+        if role == admin_role && role != 0 {
+            return Err(RbacError::InvalidSelfAdmin);
+        }
+        self.admins.insert(role, admin_role);
+        Ok(())
+    }
+
+    fn grant_role(
+        &mut self,
+        caller: usize,
+        role: usize,
+        account: usize,
+        expiry: u64,
+    ) -> Result<(), RbacError> {
+        self.require_role_exists(role)?;
+        self.require_admin(self.admins[&role], caller)?;
+        if expiry != 0 && expiry <= self.now {
+            return Err(RbacError::InvalidExpiry);
+        }
+        self.grants.insert((role, account), expiry);
+        Ok(())
+    }
+
+    fn revoke_role(&mut self, caller: usize, role: usize, account: usize) -> Result<(), RbacError> {
+        self.require_role_exists(role)?;
+        self.require_admin(self.admins[&role], caller)?;
+        self.grants.remove(&(role, account));
+        Ok(())
+    }
+
+    fn cleanup_expired_role(&mut self, role: usize, account: usize) -> bool {
+        match self.grants.get(&(role, account)) {
+            Some(&expiry) if expiry != 0 && self.now >= expiry => {
+                self.grants.remove(&(role, account));
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn member_count(&self, role: usize) -> u32 {
+        self.grants.keys().filter(|(r, _)| *r == role).count() as u32
+    }
+}
+
+/// Flatten a `try_` client result into the contract's own result.
+fn flatten<T, C>(
+    result: Result<Result<T, C>, Result<RbacError, soroban_sdk::InvokeError>>,
+) -> Result<(), RbacError> {
+    match result {
+        Ok(_) => Ok(()),
+        Err(Ok(err)) => Err(err),
+        Err(Err(err)) => panic!("unexpected host error: {err:?}"),
+    }
+}
+
+/// Apply `op` to both the contract and the model and check they agree on the result.
+fn step(env: &Env, client: &RbacContractClient, accounts: &[Address], model: &mut Model, op: &Op) {
+    match *op {
+        Op::CreateRole {
+            caller,
+            role,
+            admin_role,
+        } => {
+            let actual = flatten(client.try_create_role(
+                &accounts[caller],
+                &ROLES[role],
+                &ROLES[admin_role],
+            ));
+            assert_eq!(
+                actual,
+                model.create_role(caller, role, admin_role),
+                "{op:?}"
+            );
+        }
+        Op::SetRoleAdmin {
+            caller,
+            role,
+            admin_role,
+        } => {
+            let actual = flatten(client.try_set_role_admin(
+                &accounts[caller],
+                &ROLES[role],
+                &ROLES[admin_role],
+            ));
+            assert_eq!(
+                actual,
+                model.set_role_admin(caller, role, admin_role),
+                "{op:?}"
+            );
+        }
+        Op::GrantRole {
+            caller,
+            role,
+            account,
+            expiry,
+        } => {
+            let expiry = expiry.map_or(0, |offset| model.now + offset);
+            let actual = flatten(client.try_grant_role(
+                &accounts[caller],
+                &ROLES[role],
+                &accounts[account],
+                &expiry,
+            ));
+            assert_eq!(
+                actual,
+                model.grant_role(caller, role, account, expiry),
+                "{op:?}"
+            );
+        }
+        Op::RevokeRole {
+            caller,
+            role,
+            account,
+        } => {
+            let actual = flatten(client.try_revoke_role(
+                &accounts[caller],
+                &ROLES[role],
+                &accounts[account],
+            ));
+            assert_eq!(actual, model.revoke_role(caller, role, account), "{op:?}");
+        }
+        Op::CleanupExpiredRole { role, account } => {
+            let actual = client.cleanup_expired_role(&ROLES[role], &accounts[account]);
+            assert_eq!(actual, model.cleanup_expired_role(role, account), "{op:?}");
+        }
+        Op::AdvanceTime(seconds) => {
+            model.now += seconds;
+            env.ledger().with_mut(|li| li.timestamp = model.now);
+        }
+    }
+}
+
+/// Check the contract's observable state against the model.
+fn check_invariants(client: &RbacContractClient, accounts: &[Address], model: &Model) {
+    for (r, role) in ROLES.iter().enumerate() {
+        let exists = client.role_exists(role);
+        assert_eq!(
+            exists,
+            model.admins.contains_key(&r),
+            "role_exists({role:?})"
+        );
+        if !exists {
+            continue;
+        }
+
+        let admin_role = client.get_role_admin(role);
+        assert!(
+            client.role_exists(&admin_role),
+            "admin of {role:?} does not exist"
+        );
+        assert_eq!(
+            admin_role, ROLES[model.admins[&r]],
+            "get_role_admin({role:?})"
+        );
+        assert_eq!(client.get_role_member_count(role), model.member_count(r));
+
+        for (a, account) in accounts.iter().enumerate() {
+            assert_eq!(
+                client.has_role(role, account),
+                model.has_role(r, a),
+                "has_role({role:?}, account {a})"
+            );
+            let expiry = model.grants.get(&(r, a)).copied().unwrap_or(0);
+            assert_eq!(client.get_role_expiry(role, account), expiry);
+        }
+    }
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(64))]
+
+    #[test]
+    fn test_matches_model(ops in prop::collection::vec(op(), 1..40)) {
+        // Many `Env`s per test: skip the per-`Env` snapshot files
+        let env = Env::new_with_config(EnvTestConfig {
+            capture_snapshot_at_drop: false,
+        });
+        env.mock_all_auths();
+        env.ledger().with_mut(|li| li.timestamp = START);
+
+        let client = RbacContractClient::new(&env, &env.register(RbacContract, ()));
+        let accounts: Vec<Address> = (0..ACCOUNTS).map(|_| Address::generate(&env)).collect();
+        client.initialize(&accounts[0]);
+
+        let mut model = Model::new();
+        check_invariants(&client, &accounts, &model);
+        for op in &ops {
+            step(&env, &client, &accounts, &mut model, op);
+            check_invariants(&client, &accounts, &model);
+        }
+    }
+}
@@ -36,9 +36,14 @@ echo
 # Clean Rust artifacts
 echo "Cleaning Rust artifacts..."
 rm -rf rbac/target
+rm -rf rbac/fuzz/target rbac/fuzz/corpus rbac/fuzz/artifacts
 rm -rf examples/secure_vault/target
+rm -rf examples/role_token/target
+rm -rf examples/governance/target
 rm -f rbac/Cargo.lock
 rm -f examples/secure_vault/Cargo.lock
+rm -f examples/role_token/Cargo.lock
+rm -f examples/governance/Cargo.lock
 echo -e "${GREEN}✓ Rust artifacts cleaned${NC}"
 
 # Clean JS SDK